@@ -1,24 +1,189 @@
 use alloc::vec::Vec;
 
-use crate::CryptographicPrimitive;
+use crate::{
+    CryptographicPrimitive,
+    error::{Error, Result},
+};
 
 pub trait BlockTransformation: CryptographicPrimitive {
     fn block_size(&self) -> usize;
-    fn transform(&self, block: &[u8]) -> Vec<u8>;
+
+    /// Returns the length of the key, in bytes, e.g. 32 for AES-256.  Modes of operation use it
+    /// to validate keys before use.  The key length is not the security level, which is reported
+    /// by [`crate::runes::Rune::SecurityBits`] in `security_properties()`, but the latter can
+    /// never exceed `key_size() * 8`.
+    fn key_size(&self) -> usize;
+
+    /// Transforms a single block.  Fails with [`crate::error::Error::InvalidBlockSize`] if
+    /// `block` is not exactly `block_size()` bytes.
+    fn transform(&self, block: &[u8]) -> Result<Vec<u8>>;
+
+    /// Transforms a single block in place, without allocating.  Fails with
+    /// [`crate::error::Error::InvalidBlockSize`] if `block` is not exactly `block_size()` bytes,
+    /// in which case `block` is left unchanged.
+    ///
+    /// The default implementation calls [`BlockTransformation::transform`] and copies the result
+    /// back, so it allocates; implementations for constrained devices should override it.
+    fn transform_in_place(&self, block: &mut [u8]) -> Result<()> {
+        let output = self.transform(block)?;
+        if output.len() != block.len() {
+            return Err(Error::InvalidBlockSize(format!(
+                "Transformed {} bytes into {}",
+                block.len(),
+                output.len()
+            )));
+        }
+        block.copy_from_slice(&output);
+        Ok(())
+    }
 }
 
 pub trait BlockCipherEncrypt: BlockTransformation {
-    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
         self.transform(plaintext)
     }
 }
 
 pub trait BlockCipherDecrypt: BlockTransformation {
-    fn decrypt(&self, ciphertext: &[u8]) -> Vec<u8> {
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
         self.transform(ciphertext)
     }
 }
 
 pub trait TweakableBlockTransformation: BlockTransformation {
     fn tweak_size(&self) -> usize;
+
+    /// Transforms `block` under `tweak`, as for AES-XTS or a tweakable PRP.  Fails with
+    /// [`crate::error::Error::InvalidBlockSize`] if `block` is not exactly `block_size()` bytes,
+    /// and with [`crate::error::Error::InvalidParameter`] if `tweak` is not exactly
+    /// `tweak_size()` bytes.
+    fn transform_tweaked(&self, tweak: &[u8], block: &[u8]) -> Result<Vec<u8>>;
+}
+
+pub trait TweakableBlockCipherEncrypt: TweakableBlockTransformation {
+    fn encrypt_tweaked(&self, tweak: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        self.transform_tweaked(tweak, plaintext)
+    }
+}
+
+pub trait TweakableBlockCipherDecrypt: TweakableBlockTransformation {
+    fn decrypt_tweaked(&self, tweak: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        self.transform_tweaked(tweak, ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runes::{Rune, Schema, SchemaBuilder};
+
+    /// A toy tweakable cipher on 4-byte blocks that XORs each block with its key and tweak, so
+    /// that encryption and decryption are the same transformation.
+    struct XorCipher {
+        key: [u8; 4],
+    }
+
+    impl CryptographicPrimitive for XorCipher {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new().security_bits(32).build()
+        }
+    }
+
+    impl BlockTransformation for XorCipher {
+        fn block_size(&self) -> usize {
+            4
+        }
+
+        fn key_size(&self) -> usize {
+            4
+        }
+
+        fn transform(&self, block: &[u8]) -> Result<Vec<u8>> {
+            self.transform_tweaked(&[0; 2], block)
+        }
+    }
+
+    impl TweakableBlockTransformation for XorCipher {
+        fn tweak_size(&self) -> usize {
+            2
+        }
+
+        fn transform_tweaked(&self, tweak: &[u8], block: &[u8]) -> Result<Vec<u8>> {
+            if block.len() != self.block_size() {
+                return Err(Error::InvalidBlockSize(format!("{} byte block", block.len())));
+            }
+            if tweak.len() != self.tweak_size() {
+                return Err(Error::InvalidParameter(format!("{} byte tweak", tweak.len())));
+            }
+            Ok(block
+                .iter()
+                .zip(self.key)
+                .zip(tweak.iter().cycle())
+                .map(|((byte, key), tweak)| byte ^ key ^ tweak)
+                .collect())
+        }
+    }
+
+    impl BlockCipherEncrypt for XorCipher {}
+    impl BlockCipherDecrypt for XorCipher {}
+    impl TweakableBlockCipherEncrypt for XorCipher {}
+    impl TweakableBlockCipherDecrypt for XorCipher {}
+
+    #[test]
+    fn tweaked_encryption_round_trips_and_depends_on_the_tweak() {
+        let cipher = XorCipher { key: [1, 2, 3, 4] };
+        let plaintext = [0x10, 0x20, 0x30, 0x40];
+
+        let ciphertext = cipher.encrypt_tweaked(&[5, 6], &plaintext).unwrap();
+
+        assert_eq!(ciphertext, [0x14, 0x24, 0x36, 0x42]);
+        assert_ne!(cipher.encrypt_tweaked(&[5, 7], &plaintext).unwrap(), ciphertext);
+        assert_eq!(cipher.decrypt_tweaked(&[5, 6], &ciphertext).unwrap(), plaintext);
+        assert!(matches!(
+            cipher.encrypt_tweaked(&[5], &plaintext),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            cipher.decrypt_tweaked(&[5, 6], &ciphertext[..3]),
+            Err(Error::InvalidBlockSize(_))
+        ));
+    }
+
+    #[test]
+    fn wrong_block_sizes_are_errors() {
+        let cipher = XorCipher { key: [1, 2, 3, 4] };
+
+        assert_eq!(cipher.encrypt(&[0; 4]).unwrap(), [1, 2, 3, 4]);
+        assert_eq!(cipher.decrypt(&[1, 2, 3, 4]).unwrap(), [0; 4]);
+        for block in [&[][..], &[0; 3], &[0; 5]] {
+            assert!(matches!(cipher.encrypt(block), Err(Error::InvalidBlockSize(_))));
+            assert!(matches!(cipher.decrypt(block), Err(Error::InvalidBlockSize(_))));
+        }
+    }
+
+    #[test]
+    fn security_bits_never_exceed_the_key_length() {
+        let cipher = XorCipher { key: [1, 2, 3, 4] };
+        let schema = cipher.security_properties();
+
+        assert_eq!(cipher.key_size(), cipher.key.len());
+        match schema.get(Rune::SecurityBits(0).variant_index()) {
+            Some(Rune::SecurityBits(bits)) => assert!(usize::from(*bits) <= cipher.key_size() * 8),
+            other => panic!("Unexpected security bits {:?}", other),
+        }
+    }
+
+    #[test]
+    fn in_place_transformation_matches_transform() {
+        let cipher = XorCipher { key: [1, 2, 3, 4] };
+        let mut block = [0x10, 0x20, 0x30, 0x40];
+
+        cipher.transform_in_place(&mut block).unwrap();
+        assert_eq!(block, [0x11, 0x22, 0x33, 0x44]);
+        assert_eq!(cipher.transform(&[0x10, 0x20, 0x30, 0x40]).unwrap(), block);
+
+        let mut short = [0x10, 0x20, 0x30];
+        assert!(matches!(cipher.transform_in_place(&mut short), Err(Error::InvalidBlockSize(_))));
+        assert_eq!(short, [0x10, 0x20, 0x30]);
+    }
 }
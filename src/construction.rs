@@ -1,29 +1,943 @@
-use alloc::{boxed::Box, collections::btree_map::BTreeMap, string::String};
+use alloc::{boxed::Box, collections::btree_map::BTreeMap, string::String, vec::Vec};
 
-use crate::runes::Schema;
+#[cfg(feature = "metrics")]
+use crate::metrics::{MetricsSink, NoopSink};
+use crate::{
+    error::{Error, Result},
+    runes::{RuneComparator, RuneComparators, Schema},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct ConstructionIdentifier(String);
 
+impl ConstructionIdentifier {
+    pub fn new(identifier: impl Into<String>) -> Self {
+        Self(identifier.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The value of a construction parameter.  See [`ConstructionParams`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParamValue {
+    /// A numeric parameter, such as a key size in bits or a number of rounds.
+    Unsigned(u64),
+    /// A named parameter, such as an elliptic curve.
+    Name(String),
+}
+
+/// A construction together with the parameters to instantiate it with, so that variants such as
+/// AES with a 128- or 256-bit key can be selected without a separate identifier for each.
+/// Parameters are validated with [`Construction::check_params`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstructionParams {
+    construction: ConstructionIdentifier,
+    params: BTreeMap<String, ParamValue>,
+}
+
+impl ConstructionParams {
+    /// Creates parameters for `construction`, initially empty, so that the construction's
+    /// defaults are used.
+    pub fn new(construction: ConstructionIdentifier) -> Self {
+        Self { construction, params: BTreeMap::new() }
+    }
+
+    /// Sets the parameter `name` to `value`, replacing any earlier value.
+    pub fn with(mut self, name: &str, value: ParamValue) -> Self {
+        self.params.insert(name.into(), value);
+        self
+    }
+
+    pub fn construction(&self) -> &ConstructionIdentifier {
+        &self.construction
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ParamValue> {
+        self.params.get(name)
+    }
+}
+
+/// The number of bytes a seal operation adds to the plaintext, so callers can size buffers
+/// exactly.  For example, AES-GCM with a 96-bit nonce and 128-bit tag has 12 bytes of variation
+/// and 16 bytes of tag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Overhead {
+    /// The size of the variation parameter (nonce, IV or tweak), as reported by
+    /// [`crate::provider::Operation::variation_size`].
+    pub variation_bytes: usize,
+    /// The size of the authentication tag.
+    pub tag_bytes: usize,
+    /// Any other bytes added by the message format, such as headers or length prefixes.
+    pub framing_bytes: usize,
+}
+
+impl Overhead {
+    pub fn total(&self) -> usize {
+        self.variation_bytes + self.tag_bytes + self.framing_bytes
+    }
+}
+
+/// A known-answer test vector for a construction: the output a correct implementation must
+/// produce when sealing `input` with `key`.  See [`Construction::known_answer_tests`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownAnswerTest {
+    /// A short description of the vector, such as its source, used in error messages.
+    pub name: &'static str,
+    pub key: &'static [u8],
+    pub input: &'static [u8],
+    pub expected_output: &'static [u8],
+}
+
 pub trait Construction {
     fn identifier(&self) -> ConstructionIdentifier;
     fn schema(&self) -> Schema;
+
+    /// Returns the number of bytes a seal operation with this construction adds to the plaintext.
+    /// The default implementation reports no overhead, so constructions that add a variation,
+    /// tag or framing to the sealed data must override it.
+    fn overhead(&self) -> Overhead {
+        Overhead::default()
+    }
+
+    /// Returns the parameters the construction accepts, each with its permitted values.
+    /// Parameters that are not specified take the construction's default.  The default
+    /// implementation accepts no parameters.
+    fn accepted_params(&self) -> BTreeMap<&'static str, Vec<ParamValue>> {
+        BTreeMap::new()
+    }
+
+    /// Checks that `params` are for this construction and specify only accepted parameters with
+    /// permitted values, as reported by [`Construction::accepted_params`].
+    fn check_params(&self, params: &ConstructionParams) -> Result<()> {
+        let identifier = self.identifier();
+        if params.construction != identifier {
+            return Err(Error::InvalidParameter(format!(
+                "Parameters for {} supplied to construction {}",
+                params.construction.0, identifier.0
+            )));
+        }
+        let accepted = self.accepted_params();
+        for (name, value) in &params.params {
+            match accepted.get(name.as_str()) {
+                None => {
+                    return Err(Error::InvalidParameter(format!(
+                        "Construction {} has no parameter {}",
+                        identifier.0, name
+                    )));
+                }
+                Some(values) if !values.contains(value) => {
+                    return Err(Error::InvalidParameter(format!(
+                        "Construction {} does not support {} = {:?}",
+                        identifier.0, name, value
+                    )));
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the known-answer test vectors for this construction, which providers run as a
+    /// power-on self test with [`ConstructionRegistry::self_test`].  The default implementation
+    /// returns none.
+    fn known_answer_tests(&self) -> &[KnownAnswerTest] {
+        &[]
+    }
+
+    /// Returns the length in bytes of raw key material accepted by
+    /// [`crate::provider::Provider::import_raw`], or `None` if the construction does not permit
+    /// importing raw key material.
+    fn raw_key_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Checks that `key_material` can be imported for use with this construction.
+    fn check_raw_key(&self, key_material: &[u8]) -> Result<()> {
+        match self.raw_key_size() {
+            None => Err(Error::Unsupported(format!(
+                "Construction {} does not permit raw key import",
+                self.identifier().0
+            ))),
+            Some(size) if size != key_material.len() => Err(Error::InvalidKeyMaterial(format!(
+                "Expected {} bytes of key material, got {}",
+                size,
+                key_material.len()
+            ))),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+/// The differences between two [`ConstructionRegistry`]s, as computed by
+/// [`ConstructionRegistry::diff`].  Each list is sorted by identifier.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegistryDiff {
+    /// Constructions present only in the newer registry.
+    pub added: Vec<ConstructionIdentifier>,
+    /// Constructions present only in the older registry.
+    pub removed: Vec<ConstructionIdentifier>,
+    /// Constructions present in both registries, whose schemas differ.
+    pub changed: Vec<ConstructionIdentifier>,
+}
+
+impl RegistryDiff {
+    /// Returns true if the registries offer the same constructions with the same schemas.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
 }
 
 pub struct ConstructionRegistry {
     constructions: BTreeMap<ConstructionIdentifier, Box<dyn Construction>>,
+    comparators: RuneComparators,
+    #[cfg(feature = "metrics")]
+    metrics: Box<dyn MetricsSink>,
+}
+
+impl Default for ConstructionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ConstructionRegistry {
     pub fn new() -> Self {
-        Self { constructions: BTreeMap::new() }
+        Self {
+            constructions: BTreeMap::new(),
+            comparators: RuneComparators::new(),
+            #[cfg(feature = "metrics")]
+            metrics: Box::new(NoopSink),
+        }
+    }
+
+    /// Creates a registry of the crate's built-in constructions that are enabled by features,
+    /// ready for [`ConstructionRegistry::find_satisfying`] and
+    /// [`ConstructionRegistry::best_match`].  No built-ins are feature-enabled yet, so the
+    /// registry is empty; register the constructions of a backend with
+    /// [`ConstructionRegistry::register`].
+    pub fn with_builtins() -> Self {
+        Self::new()
+    }
+
+    /// Replaces the sink that receives metrics events, which is [`NoopSink`] by default.
+    #[cfg(feature = "metrics")]
+    pub fn set_metrics_sink(&mut self, sink: Box<dyn MetricsSink>) {
+        self.metrics = sink;
+    }
+
+    /// Returns the sink that receives metrics events.  The default implementation of
+    /// [`crate::provider::Provider::forge`] reports the selected construction to it, and
+    /// [`crate::provider::BindRune`]s forged from this registry should return it from
+    /// [`crate::provider::BindRune::metrics_sink`] so that the outcomes of their operations are
+    /// reported too.
+    #[cfg(feature = "metrics")]
+    pub fn metrics_sink(&self) -> &dyn MetricsSink {
+        self.metrics.as_ref()
     }
 
     pub fn register(&mut self, construction: Box<dyn Construction>) {
         self.constructions.insert(construction.identifier(), construction);
     }
 
-    pub fn get(&self, identifier: ConstructionIdentifier) -> Option<&Box<dyn Construction>> {
-        self.constructions.get(&identifier)
+    /// Registers `comparator` as the rule for assessing [`crate::runes::Rune::Extension`]s with
+    /// the specified `id` whenever the registry matches constructions against requirements,
+    /// replacing any rule registered earlier.  See [`RuneComparators::register`].
+    pub fn register_comparator(&mut self, id: u16, comparator: Box<dyn RuneComparator>) {
+        self.comparators.register(id, comparator);
+    }
+
+    /// Returns the comparison rules used to match constructions against requirements, e.g. for
+    /// use with [`Schema::satisfies_with`].
+    pub fn comparators(&self) -> &RuneComparators {
+        &self.comparators
+    }
+
+    pub fn get(&self, identifier: &ConstructionIdentifier) -> Option<&dyn Construction> {
+        self.constructions.get(identifier).map(Box::as_ref)
+    }
+
+    /// Returns the registered constructions, ordered by identifier.
+    pub fn iter(&self) -> impl Iterator<Item = (&ConstructionIdentifier, &dyn Construction)> {
+        self.constructions
+            .iter()
+            .map(|(identifier, construction)| (identifier, construction.as_ref()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.constructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constructions.is_empty()
+    }
+
+    pub fn contains(&self, identifier: &ConstructionIdentifier) -> bool {
+        self.constructions.contains_key(identifier)
+    }
+
+    /// Returns the constructions that were added, removed or changed in `other` relative to this
+    /// registry, for reviewing the effect of upgrading the crate or switching providers.  A
+    /// construction is changed if both registries contain its identifier but report different
+    /// schemas for it.
+    pub fn diff(&self, other: &ConstructionRegistry) -> RegistryDiff {
+        let mut diff = RegistryDiff::default();
+        for (identifier, construction) in &self.constructions {
+            match other.constructions.get(identifier) {
+                None => diff.removed.push(identifier.clone()),
+                Some(newer) if newer.schema() != construction.schema() => {
+                    diff.changed.push(identifier.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        diff.added = other
+            .constructions
+            .keys()
+            .filter(|identifier| !self.constructions.contains_key(*identifier))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Runs the known-answer tests of every registered construction, in identifier order, using
+    /// `run` to compute each output with the provider's implementation of the construction.
+    /// Fails with [`Error::SelfTestFailed`] naming the first vector whose output differs from the
+    /// expected output, or with the error returned by `run`.  Providers must not forge
+    /// [`crate::provider::BindRune`]s if the self test fails.
+    pub fn self_test(
+        &self,
+        run: impl Fn(&dyn Construction, &KnownAnswerTest) -> Result<Vec<u8>>,
+    ) -> Result<()> {
+        for (identifier, construction) in &self.constructions {
+            for test in construction.known_answer_tests() {
+                let output = run(construction.as_ref(), test)
+                    .map_err(|error| error.with_context(identifier.as_str()))?;
+                if output != test.expected_output {
+                    return Err(Error::SelfTestFailed(format!(
+                        "Construction {} failed known-answer test {}",
+                        identifier.0, test.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the strongest capabilities the registry can deliver, computed as the per-axis
+    /// maximum over all registered constructions.  For example, if one construction provides 192
+    /// security bits and another provides quantum resistance, the result reports both.
+    ///
+    /// This is an upper bound, not the schema of any single construction.  A requirement that
+    /// exceeds it on any axis cannot be satisfied by this registry, which callers can check with
+    /// [`Schema::delta`] before attempting to forge, but a requirement within it is not
+    /// necessarily satisfiable.
+    pub fn max_capabilities(&self) -> Schema {
+        Schema::upper_bound(self.constructions.values().map(|construction| construction.schema()))
+    }
+
+    /// Returns all registered constructions that satisfy `requirements`, ordered by
+    /// [`ConstructionIdentifier`].  Constructions denied by `requirements` are excluded.
+    pub fn find_satisfying(&self, requirements: &Schema) -> Vec<&dyn Construction> {
+        self.constructions
+            .iter()
+            .filter(|(identifier, construction)| {
+                !requirements.denies(identifier)
+                    && construction.schema().satisfies_with(requirements, &self.comparators)
+            })
+            .map(|(_, construction)| construction.as_ref())
+            .collect()
+    }
+
+    /// Returns the satisfying construction with the least overhead.  Equivalent to
+    /// [`ConstructionRegistry::cheapest_satisfying`].
+    pub fn find_best(&self, requirements: &Schema) -> Option<&dyn Construction> {
+        self.cheapest_satisfying(requirements)
+    }
+
+    /// Returns the cheapest registered construction that satisfies `requirements`, or `None` if
+    /// no construction satisfies them.  This serves throughput-sensitive callers who need a floor
+    /// of security but otherwise want the least costly option.
+    ///
+    /// Cost is measured by [`Overhead::total`], the number of bytes each seal adds, which is the
+    /// only cost that constructions report.  Ties are broken as in
+    /// [`ConstructionRegistry::best_match`], by margin and then by identifier.  Unlike
+    /// `best_match`, preferences are ignored.  Constructions denied by `requirements` are never
+    /// returned.
+    pub fn cheapest_satisfying(&self, requirements: &Schema) -> Option<&dyn Construction> {
+        self.constructions
+            .iter()
+            .map(|(identifier, construction)| (identifier, construction, construction.schema()))
+            .filter(|(identifier, _, _)| !requirements.denies(identifier))
+            .filter(|(_, _, schema)| schema.satisfies_with(requirements, &self.comparators))
+            .min_by_key(|(identifier, construction, schema)| {
+                (
+                    construction.overhead().total(),
+                    schema.margin_over(requirements, &self.comparators),
+                    *identifier,
+                )
+            })
+            .map(|(_, construction, _)| construction.as_ref())
+    }
+
+    /// Returns the registered construction that best matches `requirements`, as chosen by
+    /// [`ConstructionRegistry::best_match`].
+    ///
+    /// If no construction satisfies them, fails with [`Error::UnsatisfiableRequirements`]
+    /// carrying the runes of `requirements` that no registered construction provides, i.e. the
+    /// [`Schema::delta`] from [`ConstructionRegistry::max_capabilities`].  For example, a request
+    /// for quantum resistance made to a registry of classical constructions reports
+    /// [`crate::runes::Rune::QuantumResistance`].  If every rune is provided by some construction,
+    /// but no single construction provides them all, the error carries all of `requirements`.
+    pub fn select(&self, requirements: &Schema) -> Result<&dyn Construction> {
+        self.best_match(requirements).ok_or_else(|| {
+            let max_capabilities = self.max_capabilities();
+            if max_capabilities.satisfies_with(requirements, &self.comparators) {
+                Error::UnsatisfiableRequirements(requirements.clone())
+            } else {
+                Error::UnsatisfiableRequirements(
+                    max_capabilities.delta_with(requirements, &self.comparators),
+                )
+            }
+        })
+    }
+
+    /// Returns the registered construction that best matches `requirements`, or `None` if no
+    /// construction satisfies them.  Constructions denied by `requirements`, with
+    /// [`crate::runes::SchemaBuilder::deny_construction`], are never returned.
+    ///
+    /// Of the satisfying constructions, those that meet the most of the requirements'
+    /// [`Schema::preferences`] are considered first.  Among those, the one that exceeds the
+    /// requirements by the least margin is chosen, so that callers don't pay for capabilities
+    /// they didn't ask for.  The margin is the number of reported runes that exceed a requirement
+    /// or that weren't requested at all, with ties broken in favor of fewer reported security
+    /// bits.  Constructions that are still tied are ordered by [`ConstructionIdentifier`], and
+    /// the lexicographically first is chosen.  Selection therefore depends only on the registered
+    /// constructions, not on registration order, so forges are reproducible across runs and crate
+    /// versions.
+    ///
+    /// A schema with no security requirements, such as the result of `SchemaBuilder::new().build()`
+    /// which contains only the default limits and variation strategy, is not rejected.  It selects
+    /// the weakest construction that supports the defaults.
+    ///
+    /// This is a query, so no metrics are reported; the selection is reported when a key is
+    /// actually forged with it.
+    pub fn best_match(&self, requirements: &Schema) -> Option<&dyn Construction> {
+        self.constructions
+            .iter()
+            .map(|(identifier, construction)| (identifier, construction, construction.schema()))
+            .filter(|(identifier, _, _)| !requirements.denies(identifier))
+            .filter(|(_, _, schema)| schema.satisfies_with(requirements, &self.comparators))
+            .min_by_key(|(identifier, _, schema)| {
+                (
+                    schema.unmet_preferences(requirements, &self.comparators),
+                    schema.margin_over(requirements, &self.comparators),
+                    *identifier,
+                )
+            })
+            .map(|(_, construction, _)| construction.as_ref())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::runes::tests::AtLeast;
+    use crate::runes::{IsolationLevel, Rune, SchemaBuilder};
+
+    /// A construction that reports whatever the test configures.
+    pub(crate) struct TestConstruction {
+        pub(crate) identifier: &'static str,
+        pub(crate) schema: Schema,
+        pub(crate) overhead: Overhead,
+        pub(crate) raw_key_size: Option<usize>,
+        pub(crate) accepted_params: BTreeMap<&'static str, Vec<ParamValue>>,
+        pub(crate) known_answer_tests: &'static [KnownAnswerTest],
+    }
+
+    impl TestConstruction {
+        pub(crate) fn new(identifier: &'static str, schema: Schema) -> Self {
+            Self {
+                identifier,
+                schema,
+                overhead: Overhead::default(),
+                raw_key_size: None,
+                accepted_params: BTreeMap::new(),
+                known_answer_tests: &[],
+            }
+        }
+    }
+
+    impl Construction for TestConstruction {
+        fn identifier(&self) -> ConstructionIdentifier {
+            ConstructionIdentifier::new(self.identifier)
+        }
+
+        fn schema(&self) -> Schema {
+            self.schema.clone()
+        }
+
+        fn overhead(&self) -> Overhead {
+            self.overhead
+        }
+
+        fn raw_key_size(&self) -> Option<usize> {
+            self.raw_key_size
+        }
+
+        fn accepted_params(&self) -> BTreeMap<&'static str, Vec<ParamValue>> {
+            self.accepted_params.clone()
+        }
+
+        fn known_answer_tests(&self) -> &[KnownAnswerTest] {
+            self.known_answer_tests
+        }
+    }
+
+    /// Returns the schema of a construction with the default limits and `security_bits`.
+    pub(crate) fn schema_with_bits(security_bits: u8) -> Schema {
+        SchemaBuilder::new().security_bits(security_bits).build()
+    }
+
+    pub(crate) fn registry(
+        constructions: impl IntoIterator<Item = TestConstruction>,
+    ) -> ConstructionRegistry {
+        let mut registry = ConstructionRegistry::new();
+        for construction in constructions {
+            registry.register(Box::new(construction));
+        }
+        registry
+    }
+
+    #[test]
+    fn default_schema_selects_weakest_construction() {
+        let registry = registry([
+            TestConstruction::new("strong", schema_with_bits(192)),
+            TestConstruction::new("weak", schema_with_bits(128)),
+        ]);
+
+        let selected = registry.select(&SchemaBuilder::new().build()).unwrap();
+
+        assert_eq!(selected.identifier().as_str(), "weak");
+    }
+
+    #[test]
+    fn default_schema_requires_default_limits() {
+        let limited = SchemaBuilder::new().security_bits(128).message_limit(16).unwrap().build();
+        let registry = registry([TestConstruction::new("limited", limited)]);
+
+        assert!(registry.best_match(&SchemaBuilder::new().build()).is_none());
+    }
+
+    #[test]
+    fn max_capabilities_bounds_every_axis() {
+        let registry = registry([
+            TestConstruction::new("classical", schema_with_bits(192)),
+            TestConstruction::new(
+                "post-quantum",
+                SchemaBuilder::new().security_bits(128).quantum_resistance(true).build(),
+            ),
+        ]);
+        let max = registry.max_capabilities();
+        let request = SchemaBuilder::new().security_bits(255).quantum_resistance(true).build();
+
+        assert_eq!(max.delta(&request).runes(), [Rune::SecurityBits(255)]);
+        assert!(registry.select(&request).is_err());
+
+        let within = SchemaBuilder::new().security_bits(192).quantum_resistance(true).build();
+        assert!(max.satisfies(&within));
+        assert!(registry.best_match(&within).is_none());
+    }
+
+    #[test]
+    fn ties_are_broken_by_identifier() {
+        for identifiers in [["aes-gcm-a", "aes-gcm-b"], ["aes-gcm-b", "aes-gcm-a"]] {
+            let registry = registry(
+                identifiers
+                    .map(|identifier| TestConstruction::new(identifier, schema_with_bits(128))),
+            );
+
+            for _ in 0..3 {
+                let requirements = schema_with_bits(128);
+                assert_eq!(
+                    registry.best_match(&requirements).unwrap().identifier().as_str(),
+                    "aes-gcm-a"
+                );
+                assert_eq!(
+                    registry.cheapest_satisfying(&requirements).unwrap().identifier().as_str(),
+                    "aes-gcm-a"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn min_tag_bits_rejects_short_tags() {
+        let tagged =
+            |tag_bits| SchemaBuilder::new().security_bits(128).min_tag_bits(tag_bits).build();
+        let registry = registry([
+            TestConstruction::new("short-tag", tagged(128)),
+            TestConstruction::new("long-tag", tagged(256)),
+        ]);
+
+        let selected = registry.select(&SchemaBuilder::new().min_tag_bits(256).build()).unwrap();
+
+        assert_eq!(selected.identifier().as_str(), "long-tag");
+        assert!(!tagged(128).satisfies(&tagged(256)));
+        assert!(tagged(256).satisfies(&schema_with_bits(128)));
+    }
+
+    #[test]
+    fn gcm_style_overhead_is_nonce_plus_tag() {
+        let gcm = TestConstruction {
+            overhead: Overhead { variation_bytes: 12, tag_bytes: 16, framing_bytes: 0 },
+            ..TestConstruction::new("aes-128-gcm", schema_with_bits(128))
+        };
+
+        assert_eq!(gcm.overhead().total(), 28);
+
+        struct Unauthenticated;
+        impl Construction for Unauthenticated {
+            fn identifier(&self) -> ConstructionIdentifier {
+                ConstructionIdentifier::new("aes-128-ctr")
+            }
+            fn schema(&self) -> Schema {
+                schema_with_bits(128)
+            }
+        }
+        assert_eq!(Unauthenticated.overhead(), Overhead::default());
+    }
+
+    #[test]
+    fn unavailable_preferences_do_not_prevent_selection() {
+        let quantum_resistant =
+            SchemaBuilder::new().security_bits(192).quantum_resistance(true).build();
+        let classical = registry([
+            TestConstruction::new("aes-128", schema_with_bits(128)),
+            TestConstruction::new("aes-192", schema_with_bits(192)),
+        ]);
+        let requirements =
+            SchemaBuilder::new().security_bits(128).prefer_quantum_resistance().build();
+
+        assert_eq!(classical.select(&requirements).unwrap().identifier().as_str(), "aes-128");
+
+        let mixed = registry([
+            TestConstruction::new("aes-128", schema_with_bits(128)),
+            TestConstruction::new("ml-kem", quantum_resistant),
+        ]);
+        assert_eq!(mixed.select(&requirements).unwrap().identifier().as_str(), "ml-kem");
+    }
+
+    #[test]
+    fn classical_registry_rejects_quantum_requests() {
+        let classical = registry([TestConstruction::new("x25519", schema_with_bits(128))]);
+        let quantum = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+
+        let error = classical.select(&quantum).err().unwrap();
+
+        assert_eq!(error.unsatisfied_schema().unwrap().runes(), [Rune::QuantumResistance]);
+    }
+
+    #[test]
+    fn hybrid_construction_satisfies_classical_and_quantum_requests() {
+        let hybrid = SchemaBuilder::new().security_bits(192).quantum_resistance(true).build();
+        let registry = registry([TestConstruction::new("x25519-ml-kem-768", hybrid)]);
+        let quantum = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+        let classical = schema_with_bits(192);
+
+        assert_eq!(registry.select(&quantum).unwrap().identifier().as_str(), "x25519-ml-kem-768");
+        assert_eq!(registry.select(&classical).unwrap().identifier().as_str(), "x25519-ml-kem-768");
+    }
+
+    #[test]
+    fn registry_diff_reports_added_removed_and_changed() {
+        let old = registry([
+            TestConstruction::new("aes-128", schema_with_bits(128)),
+            TestConstruction::new("aes-192", schema_with_bits(192)),
+            TestConstruction::new("des", schema_with_bits(56)),
+        ]);
+        let new = registry([
+            TestConstruction::new("aes-128", schema_with_bits(128)),
+            TestConstruction::new("aes-192", schema_with_bits(190)),
+            TestConstruction::new("aes-256", schema_with_bits(255)),
+        ]);
+
+        let diff = old.diff(&new);
+
+        let identifiers = |identifiers: &[&str]| {
+            identifiers
+                .iter()
+                .map(|identifier| ConstructionIdentifier::new(*identifier))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(diff.added, identifiers(&["aes-256"]));
+        assert_eq!(diff.removed, identifiers(&["des"]));
+        assert_eq!(diff.changed, identifiers(&["aes-192"]));
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn registry_diff_round_trips_through_serde() {
+        let old = registry([TestConstruction::new("des", schema_with_bits(56))]);
+        let new = registry([TestConstruction::new("aes-128", schema_with_bits(128))]);
+        let diff = old.diff(&new);
+
+        let bytes = cbor4ii::serde::to_vec(Vec::new(), &diff).unwrap();
+
+        assert_eq!(cbor4ii::serde::from_slice::<RegistryDiff>(&bytes).unwrap(), diff);
+    }
+
+    #[test]
+    fn cheapest_satisfying_minimizes_overhead() {
+        let overhead = |tag_bytes| Overhead { variation_bytes: 12, tag_bytes, framing_bytes: 0 };
+        let registry = registry([
+            TestConstruction {
+                overhead: overhead(16),
+                ..TestConstruction::new("aes-256-gcm", schema_with_bits(255))
+            },
+            TestConstruction {
+                overhead: overhead(16),
+                ..TestConstruction::new("aes-128-gcm", schema_with_bits(128))
+            },
+            TestConstruction {
+                overhead: overhead(8),
+                ..TestConstruction::new("aes-128-gcm-short", schema_with_bits(128))
+            },
+            TestConstruction {
+                overhead: overhead(4),
+                ..TestConstruction::new("toy", schema_with_bits(64))
+            },
+        ]);
+        let cheapest = |requirements: &Schema| {
+            registry.cheapest_satisfying(requirements).map(|construction| construction.identifier())
+        };
+        let identifier = |identifier| Some(ConstructionIdentifier::new(identifier));
+
+        assert_eq!(cheapest(&schema_with_bits(128)), identifier("aes-128-gcm-short"));
+        assert_eq!(cheapest(&schema_with_bits(192)), identifier("aes-256-gcm"));
+        assert_eq!(
+            cheapest(
+                &SchemaBuilder::new()
+                    .security_bits(128)
+                    .deny_construction(ConstructionIdentifier::new("aes-128-gcm-short"))
+                    .build()
+            ),
+            identifier("aes-128-gcm")
+        );
+        assert_eq!(
+            cheapest(&SchemaBuilder::new().security_bits(128).quantum_resistance(true).build()),
+            None
+        );
+    }
+
+    #[test]
+    fn find_satisfying_lists_every_permitted_match() {
+        let registry = registry([
+            TestConstruction::new("aes-256", schema_with_bits(255)),
+            TestConstruction::new("aes-192", schema_with_bits(192)),
+            TestConstruction::new("aes-128", schema_with_bits(128)),
+        ]);
+        let identifiers = |requirements: &Schema| {
+            registry
+                .find_satisfying(requirements)
+                .iter()
+                .map(|construction| construction.identifier())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            identifiers(&schema_with_bits(192)),
+            [ConstructionIdentifier::new("aes-192"), ConstructionIdentifier::new("aes-256")]
+        );
+        assert_eq!(
+            identifiers(
+                &SchemaBuilder::new()
+                    .security_bits(128)
+                    .deny_construction(ConstructionIdentifier::new("aes-192"))
+                    .build()
+            ),
+            [ConstructionIdentifier::new("aes-128"), ConstructionIdentifier::new("aes-256")]
+        );
+        assert!(identifiers(&SchemaBuilder::new().quantum_resistance(true).build()).is_empty());
+        assert_eq!(
+            registry.find_best(&schema_with_bits(128)).unwrap().identifier(),
+            registry.cheapest_satisfying(&schema_with_bits(128)).unwrap().identifier()
+        );
+    }
+
+    #[test]
+    fn registry_iterates_in_identifier_order() {
+        let empty = ConstructionRegistry::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.iter().count(), 0);
+
+        let registry = registry([
+            TestConstruction::new("chacha20-poly1305", schema_with_bits(255)),
+            TestConstruction::new("aes-128", schema_with_bits(128)),
+        ]);
+        assert!(!registry.is_empty());
+        assert_eq!(registry.len(), 2);
+        assert!(registry.contains(&ConstructionIdentifier::new("aes-128")));
+        assert!(!registry.contains(&ConstructionIdentifier::new("aes-256")));
+        let identifiers: Vec<_> = registry
+            .iter()
+            .map(|(identifier, construction)| {
+                assert_eq!(*identifier, construction.identifier());
+                identifier.as_str()
+            })
+            .collect();
+        assert_eq!(identifiers, ["aes-128", "chacha20-poly1305"]);
+    }
+
+    #[test]
+    fn constructions_are_looked_up_by_identifier() {
+        let registry = registry([TestConstruction::new("aes-128", schema_with_bits(128))]);
+        let identifier = ConstructionIdentifier::new(String::from("aes-128"));
+
+        assert_eq!(identifier, ConstructionIdentifier::new("aes-128"));
+        assert_eq!(identifier.as_str(), "aes-128");
+        assert_eq!(registry.get(&identifier).unwrap().schema(), schema_with_bits(128));
+        assert!(registry.get(&ConstructionIdentifier::new("aes-256")).is_none());
+    }
+
+    #[test]
+    fn no_builtins_are_enabled_yet() {
+        let mut registry = ConstructionRegistry::with_builtins();
+        assert!(registry.is_empty());
+        assert!(registry.find_best(&SchemaBuilder::new().build()).is_none());
+
+        registry.register(Box::new(TestConstruction::new("aes-128", schema_with_bits(128))));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn self_test_checks_every_known_answer() {
+        const XOR_KAT: KnownAnswerTest = KnownAnswerTest {
+            name: "xor vector 1",
+            key: &[0x0f],
+            input: &[0x01, 0xf0],
+            expected_output: &[0x0e, 0xff],
+        };
+        let registry = registry([
+            TestConstruction {
+                known_answer_tests: &[XOR_KAT],
+                ..TestConstruction::new("xor", schema_with_bits(128))
+            },
+            TestConstruction::new("untested", schema_with_bits(128)),
+        ]);
+        let xor = |_: &dyn Construction, test: &KnownAnswerTest| {
+            Ok(test.input.iter().map(|byte| byte ^ test.key[0]).collect())
+        };
+
+        assert!(registry.self_test(xor).is_ok());
+        assert!(matches!(
+            registry.self_test(|_, test| Ok(test.input.to_vec())),
+            Err(Error::SelfTestFailed(message)) if message.contains("xor vector 1")
+        ));
+        assert!(matches!(
+            registry.self_test(|_, _| Err(Error::InternalError("hardware fault".into()))),
+            Err(Error::InternalError(message)) if message.contains("xor")
+        ));
+    }
+
+    #[test]
+    fn denied_constructions_are_never_selected() {
+        let registry = registry([
+            TestConstruction::new("aes-128", schema_with_bits(128)),
+            TestConstruction::new("aes-192", schema_with_bits(192)),
+        ]);
+        let deny = |identifier| {
+            SchemaBuilder::new()
+                .security_bits(128)
+                .deny_construction(ConstructionIdentifier::new(identifier))
+                .build()
+        };
+        let both = deny("aes-128").merge(&deny("aes-192")).unwrap();
+
+        assert_eq!(registry.best_match(&deny("aes-128")).unwrap().identifier().as_str(), "aes-192");
+        assert_eq!(registry.best_match(&deny("aes-192")).unwrap().identifier().as_str(), "aes-128");
+        assert!(both.denies(&ConstructionIdentifier::new("aes-128")));
+        assert!(both.denies(&ConstructionIdentifier::new("aes-192")));
+        assert!(registry.best_match(&both).is_none());
+        assert!(matches!(registry.select(&both), Err(Error::UnsatisfiableRequirements(_))));
+    }
+
+    #[test]
+    fn registered_comparators_apply_to_selection() {
+        let level =
+            |level| SchemaBuilder::new().security_bits(128).extension(7, vec![level]).build();
+        let mut registry = registry([
+            TestConstruction::new("level-1", level(1)),
+            TestConstruction::new("level-3", level(3)),
+            TestConstruction::new("level-5", level(5)),
+        ]);
+
+        assert!(registry.best_match(&level(2)).is_none());
+        registry.register_comparator(7, Box::new(AtLeast));
+
+        assert_eq!(registry.best_match(&level(2)).unwrap().identifier().as_str(), "level-3");
+        assert_eq!(registry.select(&level(4)).unwrap().identifier().as_str(), "level-5");
+        assert_eq!(
+            registry.cheapest_satisfying(&level(3)).unwrap().identifier().as_str(),
+            "level-3"
+        );
+        assert_eq!(registry.find_satisfying(&level(2)).len(), 2);
+        let error = registry.select(&level(6)).err().unwrap();
+        assert_eq!(
+            error.unsatisfied_schema().unwrap().runes(),
+            [Rune::Extension { id: 7, value: vec![6] }]
+        );
+    }
+
+    #[test]
+    fn params_are_checked_against_accepted_values() {
+        let aes = TestConstruction {
+            accepted_params: BTreeMap::from([(
+                "key_bits",
+                vec![ParamValue::Unsigned(128), ParamValue::Unsigned(256)],
+            )]),
+            ..TestConstruction::new("aes", schema_with_bits(128))
+        };
+        let params = |key_bits| {
+            ConstructionParams::new(ConstructionIdentifier::new("aes"))
+                .with("key_bits", ParamValue::Unsigned(key_bits))
+        };
+
+        assert!(aes.check_params(&params(256)).is_ok());
+        assert!(aes.check_params(&ConstructionParams::new(aes.identifier())).is_ok());
+        assert!(matches!(aes.check_params(&params(200)), Err(Error::InvalidParameter(_))));
+        assert!(matches!(
+            aes.check_params(
+                &ConstructionParams::new(aes.identifier()).with("rounds", ParamValue::Unsigned(10))
+            ),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            aes.check_params(&ConstructionParams::new(ConstructionIdentifier::new("chacha20"))),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn closed_hardware_fails_open_implementation_requirement() {
+        let hardware =
+            SchemaBuilder::new().security_bits(128).isolated(IsolationLevel::DiscreteCpu).build();
+        let open_software = SchemaBuilder::new().security_bits(128).open_implementation().build();
+        let registry = registry([
+            TestConstruction::new("secure-element", hardware.clone()),
+            TestConstruction::new("open-source", open_software.clone()),
+        ]);
+        let requirement = SchemaBuilder::new().open_implementation().build();
+
+        assert!(!hardware.satisfies(&requirement));
+        assert!(open_software.satisfies(&requirement));
+        assert_eq!(registry.select(&requirement).unwrap().identifier().as_str(), "open-source");
     }
 }
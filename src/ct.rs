@@ -0,0 +1,38 @@
+//! Constant-time helpers for comparing secret values, such as MAC tags and key commitments.
+//!
+//! A plain `==` on slices returns at the first differing byte, so the time it takes reveals how
+//! long a prefix of the secret an attacker has guessed correctly, letting them recover a valid tag
+//! one byte at a time.
+
+/// Returns true if `a` and `b` are equal, taking time independent of their contents when their
+/// lengths match.  Whether the lengths differ is not hidden, since it is usually public anyway,
+/// e.g. the tag length of a MAC.
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    // Reading the accumulator through a volatile pointer stops the optimizer from proving that the
+    // result is settled once a difference is found, and exiting the loop early.
+    // SAFETY: the pointer is derived from a reference to a live, aligned local.
+    let read = |difference: &u8| unsafe { core::ptr::read_volatile(difference) };
+    let mut difference = 0u8;
+    for (x, y) in a.iter().zip(b) {
+        difference = read(&difference) | (x ^ y);
+    }
+    read(&difference) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_slices_compare_equal() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"tag bytes", b"tag bytes"));
+        assert!(!ct_eq(b"tag bytes", b"tag byteS"));
+        assert!(!ct_eq(b"Tag bytes", b"tag bytes"));
+        assert!(!ct_eq(b"tag bytes", b"tag byte"));
+        assert!(!ct_eq(b"", b"t"));
+    }
+}
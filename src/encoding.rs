@@ -0,0 +1,66 @@
+//! Helpers for the length-prefixed binary formats produced by this crate.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::error::{Error, Result};
+
+/// Returns a value that formats `bytes` as lowercase hexadecimal, without allocating.
+pub(crate) fn hex_encode(bytes: &[u8]) -> impl fmt::Display + '_ {
+    struct Hex<'a>(&'a [u8]);
+
+    impl fmt::Display for Hex<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+        }
+    }
+
+    Hex(bytes)
+}
+
+/// Decodes hexadecimal `text`, in either case.  Returns `None` if `text` has odd length or
+/// contains anything but hex digits.
+pub(crate) fn hex_decode(text: &str) -> Option<Vec<u8>> {
+    let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    let text = text.as_bytes();
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    text.chunks_exact(2).map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?)).collect()
+}
+
+/// Appends `len` to `bytes` as a big-endian 32-bit length.
+pub(crate) fn put_length(bytes: &mut Vec<u8>, len: usize) -> Result<()> {
+    let len =
+        u32::try_from(len).map_err(|_| Error::InternalError("Field too large to encode".into()))?;
+    bytes.extend_from_slice(&len.to_be_bytes());
+    Ok(())
+}
+
+/// Appends `field` to `bytes`, preceded by its length.
+pub(crate) fn put_field(bytes: &mut Vec<u8>, field: &[u8]) -> Result<()> {
+    put_length(bytes, field.len())?;
+    bytes.extend_from_slice(field);
+    Ok(())
+}
+
+/// Removes and returns the first `len` bytes of `rest`.
+pub(crate) fn take<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    let (taken, remainder) = rest
+        .split_at_checked(len)
+        .ok_or_else(|| Error::MalformedCiphertext("Truncated input".into()))?;
+    *rest = remainder;
+    Ok(taken)
+}
+
+/// Removes a big-endian 32-bit length from the front of `rest`.
+pub(crate) fn take_length(rest: &mut &[u8]) -> Result<usize> {
+    let bytes = take(rest, 4)?;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+}
+
+/// Removes a field written by [`put_field`] from the front of `rest`.
+pub(crate) fn take_field<'a>(rest: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let len = take_length(rest)?;
+    take(rest, len)
+}
@@ -1,7 +1,9 @@
-use alloc::string::String;
+use alloc::{boxed::Box, string::String};
+use core::fmt;
 
 use crate::runes::Schema;
 
+#[derive(Debug)]
 pub enum Error {
     /// The requested schema cannot be satisfied by any available construction.
     UnsatisfiableRequirements(Schema),
@@ -34,6 +36,238 @@ pub enum Error {
     /// The provided variation type is of a type that is not supported by the
     /// [`crate::provider::BindRune`].
     VariationTypeInvalid(String),
+    /// The requested operation is not supported by the [`crate::provider::BindRune`] or
+    /// [`crate::provider::Provider`].
+    Unsupported(String),
+    /// The provided key material is not valid for the construction.
+    InvalidKeyMaterial(String),
+    /// The key material of the [`crate::provider::BindRune`] may not be exported.
+    ExportForbidden(String),
+    /// The operation is not among the usages permitted by the [`crate::runes::Rune::KeyUsage`] of
+    /// the [`crate::provider::BindRune`].
+    KeyUsageViolation(String),
+    /// The version provided to [`crate::provider::Operation::set_version`] is lower than the
+    /// highest version previously sealed by the [`crate::provider::BindRune`].
+    RollbackDetected(String),
+    /// The input to a [`crate::block_cipher::BlockTransformation`] is not exactly one block.
+    InvalidBlockSize(String),
+    /// The provided ciphertext is not correctly formatted.
+    MalformedCiphertext(String),
+    /// A construction parameter is unknown or has an unsupported value.
+    InvalidParameter(String),
+    /// Deriving the requested subkey would exceed the maximum
+    /// [`crate::runes::Rune::DerivationDepth`].
+    DerivationTooDeep(String),
+    /// The requirements contradict each other, so no construction could meet them all.  See
+    /// [`crate::runes::SchemaBuilder::try_build`].
+    ConflictingRequirements(String),
+    /// A construction produced an output that differs from one of its known-answer tests.  See
+    /// [`crate::construction::ConstructionRegistry::self_test`].
+    SelfTestFailed(String),
+    /// The data failed integrity or authenticity validation.  No further detail is given, to
+    /// avoid providing an oracle to attackers.
+    AuthenticationFailed,
+    /// An error that doesn't carry a message, annotated with context by [`Error::with_context`].
+    WithContext { context: String, error: Box<Error> },
+}
+
+impl Error {
+    /// Annotates the error with context, such as a description of the operation that failed, so
+    /// that layers of a call stack can add information without discarding the original error.
+    ///
+    /// Errors that carry a message get the context prepended to it.  Other errors, notably
+    /// [`Error::UnsatisfiableRequirements`], are wrapped in [`Error::WithContext`] so that their
+    /// payload is preserved; use [`Error::unsatisfied_schema`] to retrieve the schema.
+    pub fn with_context(self, context: &str) -> Error {
+        let annotate = |message: String| format!("{}: {}", context, message);
+        match self {
+            Error::CommunicationError(message) => Error::CommunicationError(annotate(message)),
+            Error::InternalError(message) => Error::InternalError(annotate(message)),
+            Error::InvalidVariation(message) => Error::InvalidVariation(annotate(message)),
+            Error::InvalidMessageLimit(message) => Error::InvalidMessageLimit(annotate(message)),
+            Error::InvalidMessageSizeLimit(message) => {
+                Error::InvalidMessageSizeLimit(annotate(message))
+            }
+            Error::InvalidTotalDataLimit(message) => {
+                Error::InvalidTotalDataLimit(annotate(message))
+            }
+            Error::InvalidCryptoPeriod(message) => Error::InvalidCryptoPeriod(annotate(message)),
+            Error::MessageTooLong(message) => Error::MessageTooLong(annotate(message)),
+            Error::TotalDataTooLong(message) => Error::TotalDataTooLong(annotate(message)),
+            Error::CryptoPeriodTooSoon(message) => Error::CryptoPeriodTooSoon(annotate(message)),
+            Error::CryptoPeriodTooLate(message) => Error::CryptoPeriodTooLate(annotate(message)),
+            Error::VariationInvalid(message) => Error::VariationInvalid(annotate(message)),
+            Error::VariationTypeInvalid(message) => Error::VariationTypeInvalid(annotate(message)),
+            Error::Unsupported(message) => Error::Unsupported(annotate(message)),
+            Error::InvalidKeyMaterial(message) => Error::InvalidKeyMaterial(annotate(message)),
+            Error::ExportForbidden(message) => Error::ExportForbidden(annotate(message)),
+            Error::KeyUsageViolation(message) => Error::KeyUsageViolation(annotate(message)),
+            Error::RollbackDetected(message) => Error::RollbackDetected(annotate(message)),
+            Error::InvalidBlockSize(message) => Error::InvalidBlockSize(annotate(message)),
+            Error::MalformedCiphertext(message) => Error::MalformedCiphertext(annotate(message)),
+            Error::InvalidParameter(message) => Error::InvalidParameter(annotate(message)),
+            Error::DerivationTooDeep(message) => Error::DerivationTooDeep(annotate(message)),
+            Error::ConflictingRequirements(message) => {
+                Error::ConflictingRequirements(annotate(message))
+            }
+            Error::SelfTestFailed(message) => Error::SelfTestFailed(annotate(message)),
+            Error::UnsatisfiableRequirements(_)
+            | Error::UnknownLabel
+            | Error::AuthenticationFailed
+            | Error::WithContext { .. } => {
+                Error::WithContext { context: context.into(), error: Box::new(self) }
+            }
+        }
+    }
+
+    /// Returns the schema of an [`Error::UnsatisfiableRequirements`], looking through any context
+    /// added by [`Error::with_context`].
+    pub fn unsatisfied_schema(&self) -> Option<&Schema> {
+        match self {
+            Error::UnsatisfiableRequirements(schema) => Some(schema),
+            Error::WithContext { error, .. } => error.unsatisfied_schema(),
+            _ => None,
+        }
+    }
+}
+
+/// Formats errors for `defmt` logging on embedded targets.  Messages are transmitted as strings,
+/// but variant names are interned.  Only available with the `defmt` feature.
+#[cfg(feature = "defmt")]
+impl defmt::Format for Error {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Error::UnsatisfiableRequirements(schema) => {
+                defmt::write!(f, "UnsatisfiableRequirements({})", schema)
+            }
+            Error::UnknownLabel => defmt::write!(f, "UnknownLabel"),
+            Error::CommunicationError(message) => {
+                defmt::write!(f, "CommunicationError({=str})", message)
+            }
+            Error::InternalError(message) => defmt::write!(f, "InternalError({=str})", message),
+            Error::InvalidVariation(message) => {
+                defmt::write!(f, "InvalidVariation({=str})", message)
+            }
+            Error::InvalidMessageLimit(message) => {
+                defmt::write!(f, "InvalidMessageLimit({=str})", message)
+            }
+            Error::InvalidMessageSizeLimit(message) => {
+                defmt::write!(f, "InvalidMessageSizeLimit({=str})", message)
+            }
+            Error::InvalidTotalDataLimit(message) => {
+                defmt::write!(f, "InvalidTotalDataLimit({=str})", message)
+            }
+            Error::InvalidCryptoPeriod(message) => {
+                defmt::write!(f, "InvalidCryptoPeriod({=str})", message)
+            }
+            Error::MessageTooLong(message) => defmt::write!(f, "MessageTooLong({=str})", message),
+            Error::TotalDataTooLong(message) => {
+                defmt::write!(f, "TotalDataTooLong({=str})", message)
+            }
+            Error::CryptoPeriodTooSoon(message) => {
+                defmt::write!(f, "CryptoPeriodTooSoon({=str})", message)
+            }
+            Error::CryptoPeriodTooLate(message) => {
+                defmt::write!(f, "CryptoPeriodTooLate({=str})", message)
+            }
+            Error::VariationInvalid(message) => {
+                defmt::write!(f, "VariationInvalid({=str})", message)
+            }
+            Error::VariationTypeInvalid(message) => {
+                defmt::write!(f, "VariationTypeInvalid({=str})", message)
+            }
+            Error::Unsupported(message) => defmt::write!(f, "Unsupported({=str})", message),
+            Error::InvalidKeyMaterial(message) => {
+                defmt::write!(f, "InvalidKeyMaterial({=str})", message)
+            }
+            Error::ExportForbidden(message) => defmt::write!(f, "ExportForbidden({=str})", message),
+            Error::KeyUsageViolation(message) => {
+                defmt::write!(f, "KeyUsageViolation({=str})", message)
+            }
+            Error::RollbackDetected(message) => {
+                defmt::write!(f, "RollbackDetected({=str})", message)
+            }
+            Error::InvalidBlockSize(message) => {
+                defmt::write!(f, "InvalidBlockSize({=str})", message)
+            }
+            Error::MalformedCiphertext(message) => {
+                defmt::write!(f, "MalformedCiphertext({=str})", message)
+            }
+            Error::InvalidParameter(message) => {
+                defmt::write!(f, "InvalidParameter({=str})", message)
+            }
+            Error::DerivationTooDeep(message) => {
+                defmt::write!(f, "DerivationTooDeep({=str})", message)
+            }
+            Error::ConflictingRequirements(message) => {
+                defmt::write!(f, "ConflictingRequirements({=str})", message)
+            }
+            Error::SelfTestFailed(message) => defmt::write!(f, "SelfTestFailed({=str})", message),
+            Error::AuthenticationFailed => defmt::write!(f, "AuthenticationFailed"),
+            Error::WithContext { context, error } => defmt::write!(f, "{=str}: {}", context, error),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnsatisfiableRequirements(schema) => {
+                write!(f, "Unsatisfiable requirements:\n{}", schema)
+            }
+            Error::UnknownLabel => write!(f, "Unknown label"),
+            Error::CommunicationError(message) => write!(f, "Communication error: {}", message),
+            Error::InternalError(message) => write!(f, "Internal error: {}", message),
+            Error::InvalidVariation(message) => write!(f, "Invalid variation: {}", message),
+            Error::InvalidMessageLimit(message) => write!(f, "Invalid message limit: {}", message),
+            Error::InvalidMessageSizeLimit(message) => {
+                write!(f, "Invalid message size limit: {}", message)
+            }
+            Error::InvalidTotalDataLimit(message) => {
+                write!(f, "Invalid total data limit: {}", message)
+            }
+            Error::InvalidCryptoPeriod(message) => write!(f, "Invalid crypto period: {}", message),
+            Error::MessageTooLong(message) => write!(f, "Message too long: {}", message),
+            Error::TotalDataTooLong(message) => write!(f, "Total data too long: {}", message),
+            Error::CryptoPeriodTooSoon(message) => {
+                write!(f, "Crypto period not yet begun: {}", message)
+            }
+            Error::CryptoPeriodTooLate(message) => {
+                write!(f, "Crypto period has ended: {}", message)
+            }
+            Error::VariationInvalid(message) => write!(f, "Invalid variation value: {}", message),
+            Error::VariationTypeInvalid(message) => {
+                write!(f, "Invalid variation type: {}", message)
+            }
+            Error::Unsupported(message) => write!(f, "Unsupported: {}", message),
+            Error::InvalidKeyMaterial(message) => write!(f, "Invalid key material: {}", message),
+            Error::ExportForbidden(message) => write!(f, "Export forbidden: {}", message),
+            Error::KeyUsageViolation(message) => write!(f, "Key usage violation: {}", message),
+            Error::RollbackDetected(message) => write!(f, "Rollback detected: {}", message),
+            Error::InvalidBlockSize(message) => write!(f, "Invalid block size: {}", message),
+            Error::MalformedCiphertext(message) => write!(f, "Malformed ciphertext: {}", message),
+            Error::InvalidParameter(message) => write!(f, "Invalid parameter: {}", message),
+            Error::DerivationTooDeep(message) => write!(f, "Derivation too deep: {}", message),
+            Error::ConflictingRequirements(message) => {
+                write!(f, "Conflicting requirements: {}", message)
+            }
+            Error::SelfTestFailed(message) => write!(f, "Self test failed: {}", message),
+            Error::AuthenticationFailed => write!(f, "Authentication failed"),
+            Error::WithContext { context, error } => write!(f, "{}: {}", context, error),
+        }
+    }
+}
+
+/// The source of an [`Error::WithContext`] is the error it annotates.  Errors from `jiff` are
+/// converted to [`Error::InternalError`] messages rather than kept as sources, because `jiff`
+/// only implements the standard error trait with its `std` feature.
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::WithContext { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<jiff::Error> for Error {
@@ -43,3 +277,60 @@ impl From<jiff::Error> for Error {
 }
 
 pub type Result<T> = core::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runes::SchemaBuilder;
+    use alloc::string::ToString;
+
+    #[test]
+    fn context_is_displayed_and_schema_preserved() {
+        let schema = SchemaBuilder::new().security_bits(192).build();
+
+        let error = Error::UnsatisfiableRequirements(schema.clone())
+            .with_context("selecting construction")
+            .with_context("forging \"key\"");
+
+        let message = error.to_string();
+        assert!(message.starts_with("forging \"key\": selecting construction: "));
+        assert!(message.contains("Unsatisfiable requirements"));
+        assert_eq!(error.unsatisfied_schema(), Some(&schema));
+        assert!(core::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn context_is_prepended_to_messages() {
+        let error = Error::InvalidParameter("zero iterations".into()).with_context("hashing");
+
+        assert!(matches!(
+            &error,
+            Error::InvalidParameter(message) if message == "hashing: zero iterations"
+        ));
+        assert_eq!(error.unsatisfied_schema(), None);
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn errors_and_schemas_are_defmt_formattable() {
+        fn assert_format<T: defmt::Format>(_: &T) {}
+        let schema = SchemaBuilder::new().security_bits(128).build();
+
+        assert_format(&schema);
+        assert_format(&schema.runes()[0]);
+        assert_format(&Error::UnsatisfiableRequirements(schema).with_context("forging"));
+    }
+
+    #[test]
+    fn unsatisfiable_requirements_are_displayed_one_per_line() {
+        let schema = SchemaBuilder::new().security_bits(192).quantum_resistance(true).build();
+
+        let message = Error::UnsatisfiableRequirements(schema.clone()).to_string();
+
+        assert_eq!(message, format!("Unsatisfiable requirements:\n{}", schema));
+        assert!(message.lines().any(|line| line == "security_bits: 192"));
+        assert!(message.lines().any(|line| line == "quantum_resistance"));
+        assert_eq!(Error::AuthenticationFailed.to_string(), "Authentication failed");
+        assert_eq!(Error::Unsupported("no import".into()).to_string(), "Unsupported: no import");
+    }
+}
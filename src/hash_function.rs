@@ -1 +1,184 @@
+use alloc::vec::Vec;
 
+use crate::{CryptographicPrimitive, error::Result};
+
+/// A cryptographic hash function with fixed-length output, such as SHA-256, SHA3-256 or
+/// BLAKE2b.
+///
+/// `security_properties()` should report [`crate::runes::Rune::SecurityBits`] of half the output
+/// length in bits, the generic bound on collision resistance, e.g. 128 for SHA-256.  Preimage
+/// resistance is usually the full output length, but collision resistance is what most uses
+/// depend on.
+pub trait HashFunction: CryptographicPrimitive {
+    type Context: HashContext;
+
+    /// Returns the length of the digest, in bytes.
+    fn output_size(&self) -> usize;
+
+    /// Returns the length of the blocks the compression function processes, in bytes, e.g. 64
+    /// for SHA-256.  HMAC and other constructions built on the hash depend on it.
+    fn block_size(&self) -> usize;
+
+    /// Begins hashing a message that will be supplied in chunks.
+    fn new_context(&self) -> Self::Context;
+
+    /// Returns the digest of `data`.
+    fn digest(&self, data: &[u8]) -> Vec<u8> {
+        let mut context = self.new_context();
+        context.update(data);
+        context.finalize()
+    }
+}
+
+/// A message being hashed incrementally.  See [`HashFunction::new_context`].
+pub trait HashContext {
+    /// Appends `data` to the message.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the digest of the message, of [`HashFunction::output_size`] bytes.
+    fn finalize(self) -> Vec<u8>;
+}
+
+/// An extendable-output function (XOF), such as SHAKE128, SHAKE256 or BLAKE3, which produces
+/// output of any length.  Unlike [`HashFunction`], the XOF is itself the hashing state: input is
+/// absorbed with [`ExtendableOutputFunction::update`], then output is read with any number of
+/// calls to [`ExtendableOutputFunction::squeeze`], which together produce one continuous output
+/// stream.  Squeezing 64 bytes at once or in two 32-byte halves yields the same bytes.
+///
+/// `security_properties()` should report the [`crate::runes::Rune::SecurityBits`] of the XOF
+/// itself, e.g. 128 for SHAKE128, which output lengths beyond twice that don't increase.
+pub trait ExtendableOutputFunction: CryptographicPrimitive {
+    /// Absorbs `data`.  Fails with [`crate::error::Error::InternalError`] once output has been
+    /// squeezed, since the input is then final.
+    fn update(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Fills `out` with the next bytes of output.  The first call ends the absorb phase.
+    fn squeeze(&mut self, out: &mut [u8]);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{
+        error::Error,
+        runes::{Schema, SchemaBuilder},
+    };
+
+    /// A toy hash with a non-cryptographic 64-bit FNV-1a digest.
+    struct Fnv;
+
+    struct FnvContext(u64);
+
+    impl CryptographicPrimitive for Fnv {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new().security_bits(32).build()
+        }
+    }
+
+    impl HashFunction for Fnv {
+        type Context = FnvContext;
+
+        fn output_size(&self) -> usize {
+            8
+        }
+
+        fn block_size(&self) -> usize {
+            1
+        }
+
+        fn new_context(&self) -> FnvContext {
+            FnvContext(0xcbf29ce484222325)
+        }
+    }
+
+    impl HashContext for FnvContext {
+        fn update(&mut self, data: &[u8]) {
+            for byte in data {
+                self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+            }
+        }
+
+        fn finalize(self) -> Vec<u8> {
+            self.0.to_be_bytes().to_vec()
+        }
+    }
+
+    #[test]
+    fn digest_matches_incremental_hashing() {
+        let digest = Fnv.digest(b"hello world");
+
+        assert_eq!(digest.len(), Fnv.output_size());
+        assert_eq!(Fnv.digest(b""), 0xcbf29ce484222325u64.to_be_bytes());
+        let mut context = Fnv.new_context();
+        context.update(b"hello");
+        context.update(b"");
+        context.update(b" world");
+        assert_eq!(context.finalize(), digest);
+        assert_ne!(Fnv.digest(b"hello world!"), digest);
+    }
+
+    /// A toy XOF whose output stream is derived from a non-cryptographic FNV-1a hash of the
+    /// input.
+    struct FnvXof {
+        hash: u64,
+        position: Option<u64>,
+    }
+
+    impl CryptographicPrimitive for FnvXof {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new().security_bits(32).build()
+        }
+    }
+
+    impl ExtendableOutputFunction for FnvXof {
+        fn update(&mut self, data: &[u8]) -> Result<()> {
+            if self.position.is_some() {
+                return Err(Error::InternalError("Cannot update after squeezing".into()));
+            }
+            for byte in data {
+                self.hash = (self.hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+            }
+            Ok(())
+        }
+
+        fn squeeze(&mut self, out: &mut [u8]) {
+            let position = self.position.get_or_insert(0);
+            for byte in out {
+                *byte = ((self.hash ^ *position).wrapping_mul(0x100000001b3) >> 56) as u8;
+                *position += 1;
+            }
+        }
+    }
+
+    /// Checks that XOFs made by `new` produce one continuous output stream, however it is
+    /// squeezed, that depends on the input, and that they reject input once squeezed.
+    pub(crate) fn check_xof<X: ExtendableOutputFunction>(new: impl Fn() -> X) {
+        let output = |chunks: &[usize]| {
+            let mut xof = new();
+            xof.update(b"input").unwrap();
+            let mut output = Vec::new();
+            for len in chunks {
+                let mut chunk = vec![0; *len];
+                xof.squeeze(&mut chunk);
+                output.extend(chunk);
+            }
+            output
+        };
+
+        let whole = output(&[40]);
+        assert_eq!(output(&[20, 20]), whole);
+        assert_eq!(output(&[1, 0, 7, 32]), whole);
+
+        let mut xof = new();
+        xof.update(b"other input").unwrap();
+        let mut other = [0; 40];
+        xof.squeeze(&mut other);
+        assert_ne!(other[..], whole);
+        assert!(matches!(xof.update(b"more"), Err(Error::InternalError(_))));
+    }
+
+    #[test]
+    fn xof_output_is_one_continuous_stream() {
+        check_xof(|| FnvXof { hash: 0xcbf29ce484222325, position: None });
+    }
+}
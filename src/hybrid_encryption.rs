@@ -1 +1,316 @@
+use alloc::vec::Vec;
 
+use crate::{
+    CryptographicPrimitive,
+    encoding::{put_field, put_length, take_field, take_length},
+    error::{Error, Result},
+    runes::Rune,
+    secret::Secret,
+};
+
+/// The sending half of a hybrid public-key encryption scheme, such as HPKE or ECIES, in which a
+/// key encapsulation mechanism (KEM) establishes a fresh content key with the recipient and a DEM
+/// encrypts the payload under it.
+///
+/// Implementations provide the two halves of the scheme, [`HybridEncrypt::dem_seal`] and
+/// [`HybridEncrypt::encapsulate`], from which [`HybridEncrypt::seal`] and
+/// [`HybridEncrypt::seal_multi`] are composed.  Schemes that bind the encapsulation to the
+/// payload, so that the two can't be separated, override `seal` instead, and don't support
+/// multiple recipients.
+///
+/// `security_properties()` should report [`crate::runes::Rune::PublicPrivateKeyPair`], and, for
+/// post-quantum or hybrid KEMs such as ML-KEM or X25519+ML-KEM,
+/// [`crate::runes::Rune::QuantumResistance`].
+pub trait HybridEncrypt: CryptographicPrimitive {
+    /// Encrypts `plaintext` to the holder of the private key matching `recipient_public`, and
+    /// authenticates it together with `aad`, which is not encrypted and must be supplied again to
+    /// open the ciphertext.  The result carries the encapsulated key and the DEM ciphertext,
+    /// encoded as a [`MultiRecipientCiphertext`] with a single slot.
+    fn seal(&self, recipient_public: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let (content_key, ciphertext) = self.dem_seal(plaintext, aad)?;
+        let slots = vec![self.encapsulate(recipient_public, &content_key)?];
+        MultiRecipientCiphertext { slots, ciphertext }.to_bytes()
+    }
+
+    /// Encrypts `plaintext` once, to all of `recipients`, producing a [`MultiRecipientCiphertext`]
+    /// with one slot per recipient, in order, which any one of them can open.  Constructions that
+    /// support this report [`crate::runes::Rune::MultiRecipient`].  Fails with
+    /// [`Error::InvalidParameter`] if there are no recipients or more than the rune's `max`, and
+    /// with [`Error::Unsupported`] if the construction doesn't report the rune.
+    fn seal_multi(&self, recipients: &[&[u8]], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let properties = self.security_properties();
+        let Some(Rune::MultiRecipient { max }) =
+            properties.get(Rune::MultiRecipient { max: 0 }.variant_index())
+        else {
+            return Err(Error::Unsupported("Multi-recipient sealing is not supported".into()));
+        };
+        if recipients.is_empty() || recipients.len() > *max as usize {
+            return Err(Error::InvalidParameter(format!(
+                "Expected 1 to {} recipients, got {}",
+                max,
+                recipients.len()
+            )));
+        }
+        let (content_key, ciphertext) = self.dem_seal(plaintext, aad)?;
+        let slots = recipients
+            .iter()
+            .map(|recipient| self.encapsulate(recipient, &content_key))
+            .collect::<Result<Vec<_>>>()?;
+        MultiRecipientCiphertext { slots, ciphertext }.to_bytes()
+    }
+
+    /// Encrypts `plaintext`, authenticated together with `aad`, with the DEM under a fresh random
+    /// content key, returning the content key and the DEM ciphertext.  The default
+    /// implementation returns [`Error::Unsupported`].
+    fn dem_seal(&self, _plaintext: &[u8], _aad: &[u8]) -> Result<(Secret, Vec<u8>)> {
+        Err(Error::Unsupported("Separate DEM encryption is not supported".into()))
+    }
+
+    /// Encapsulates `content_key` to the holder of the private key matching `recipient_public`,
+    /// returning the slot to store in the [`MultiRecipientCiphertext`].  Fails with
+    /// [`Error::InvalidKeyMaterial`] if `recipient_public` is not a valid public key.  The
+    /// default implementation returns [`Error::Unsupported`].
+    fn encapsulate(&self, _recipient_public: &[u8], _content_key: &Secret) -> Result<Vec<u8>> {
+        Err(Error::Unsupported("Separate key encapsulation is not supported".into()))
+    }
+}
+
+/// The receiving half of a hybrid public-key encryption scheme, holding the private key.  See
+/// [`HybridEncrypt`].
+pub trait HybridDecrypt: CryptographicPrimitive {
+    /// Decrypts a ciphertext produced by [`HybridEncrypt::seal`] or [`HybridEncrypt::seal_multi`]
+    /// with the same `aad`, trying each slot in turn until one decapsulates.  Fails with
+    /// [`Error::MalformedCiphertext`] if `ciphertext` can't be decoded, and with
+    /// [`Error::AuthenticationFailed`] if it was not sealed to this key or has been modified.
+    ///
+    /// The default implementation recovers the content key with [`HybridDecrypt::decapsulate`]
+    /// and decrypts with [`HybridDecrypt::dem_open`], moving on to the next slot whenever either
+    /// fails with `AuthenticationFailed`.  Other errors are returned immediately.
+    fn open(&self, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let ciphertext = MultiRecipientCiphertext::from_bytes(ciphertext)?;
+        for slot in &ciphertext.slots {
+            let opened = self
+                .decapsulate(slot)
+                .and_then(|content_key| self.dem_open(&content_key, &ciphertext.ciphertext, aad));
+            match opened {
+                Err(Error::AuthenticationFailed) => continue,
+                opened => return opened,
+            }
+        }
+        Err(Error::AuthenticationFailed)
+    }
+
+    /// Recovers the content key from `slot`, one slot of a [`MultiRecipientCiphertext`].  Fails
+    /// with [`Error::AuthenticationFailed`] if the slot was not encapsulated to this key, where
+    /// the KEM can tell.  KEMs with implicit rejection, such as ML-KEM, instead return an
+    /// unrelated key, which [`HybridDecrypt::dem_open`] then rejects.  The default implementation
+    /// returns [`Error::Unsupported`].
+    fn decapsulate(&self, _slot: &[u8]) -> Result<Secret> {
+        Err(Error::Unsupported("Separate key decapsulation is not supported".into()))
+    }
+
+    /// Decrypts the DEM ciphertext `ciphertext` under `content_key`, checking that it was sealed
+    /// with `aad`.  Fails with [`Error::AuthenticationFailed`] if the key is wrong or the
+    /// ciphertext has been modified.  The default implementation returns [`Error::Unsupported`].
+    fn dem_open(&self, _content_key: &Secret, _ciphertext: &[u8], _aad: &[u8]) -> Result<Vec<u8>> {
+        Err(Error::Unsupported("Separate DEM decryption is not supported".into()))
+    }
+}
+
+/// A single payload encrypted to several recipients, in the manner of age or OpenPGP.  The payload
+/// is encrypted once, under a random content key, with a data encapsulation mechanism (DEM) such
+/// as an AEAD.  The content key is then encapsulated separately to each recipient's public key,
+/// producing one slot per recipient.  A recipient decapsulates their slot to recover the content
+/// key and then decrypts the shared DEM ciphertext, so the cost of adding a recipient is one slot,
+/// not one copy of the payload.
+///
+/// Slots carry no recipient identifiers, so a recipient must try each slot in turn.  This avoids
+/// revealing who the recipients are, at the cost of a decapsulation attempt per slot.
+///
+/// The encoded layout, produced by [`MultiRecipientCiphertext::to_bytes`], is:
+///
+/// | Field             | Size          | Contents                                         |
+/// |-------------------|---------------|--------------------------------------------------|
+/// | slot count        | 4 bytes       | number of slots `n`, big-endian                  |
+/// | slot length       | 4 bytes       | length of the following slot, big-endian         |
+/// | slot              | slot length   | the encapsulated content key for one recipient   |
+/// | ...               |               | slot length and slot, repeated `n` times in all  |
+/// | DEM ciphertext    | remainder     | the payload, encrypted under the content key     |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiRecipientCiphertext {
+    /// The encapsulated content key for each recipient, in the order the recipients were given.
+    pub slots: Vec<Vec<u8>>,
+    /// The payload, encrypted once under the content key.
+    pub ciphertext: Vec<u8>,
+}
+
+impl MultiRecipientCiphertext {
+    /// Encodes the ciphertext in the layout described on [`MultiRecipientCiphertext`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        put_length(&mut bytes, self.slots.len())?;
+        for slot in &self.slots {
+            put_field(&mut bytes, slot)?;
+        }
+        bytes.extend_from_slice(&self.ciphertext);
+        Ok(bytes)
+    }
+
+    /// Decodes a ciphertext in the layout described on [`MultiRecipientCiphertext`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut rest = bytes;
+        let count = take_length(&mut rest)?;
+        let mut slots = Vec::new();
+        for _ in 0..count {
+            slots.push(take_field(&mut rest)?.to_vec());
+        }
+        Ok(Self { slots, ciphertext: rest.to_vec() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ct::ct_eq,
+        runes::{Schema, SchemaBuilder},
+    };
+
+    /// XORs `data` with `key`, repeated as needed.
+    fn xor(key: &[u8], data: &[u8]) -> Vec<u8> {
+        data.iter().zip(key.iter().cycle()).map(|(data, key)| data ^ key).collect()
+    }
+
+    /// A non-cryptographic FNV-1a tag over `parts`.
+    fn tag(parts: &[&[u8]]) -> Vec<u8> {
+        let tag =
+            parts.iter().flat_map(|part| part.iter()).fold(0xcbf29ce484222325u64, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+            });
+        tag.to_be_bytes().to_vec()
+    }
+
+    /// A toy KEM and DEM with no security at all.  A recipient's public key is their name, which
+    /// slots carry in the clear, and the content key is fixed.
+    struct ToySender {
+        properties: Schema,
+    }
+
+    impl CryptographicPrimitive for ToySender {
+        fn security_properties(&self) -> Schema {
+            self.properties.clone()
+        }
+    }
+
+    impl HybridEncrypt for ToySender {
+        fn dem_seal(&self, plaintext: &[u8], aad: &[u8]) -> Result<(Secret, Vec<u8>)> {
+            let content_key = Secret::new(vec![0x42; 16]);
+            let mut ciphertext = xor(content_key.as_slice(), plaintext);
+            ciphertext.extend(tag(&[content_key.as_slice(), aad, &ciphertext]));
+            Ok((content_key, ciphertext))
+        }
+
+        fn encapsulate(&self, recipient_public: &[u8], content_key: &Secret) -> Result<Vec<u8>> {
+            let mut slot = Vec::new();
+            put_field(&mut slot, recipient_public)?;
+            slot.extend(xor(recipient_public, content_key.as_slice()));
+            Ok(slot)
+        }
+    }
+
+    struct ToyRecipient {
+        name: &'static [u8],
+    }
+
+    impl CryptographicPrimitive for ToyRecipient {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new().public_private_key_pair().build()
+        }
+    }
+
+    impl HybridDecrypt for ToyRecipient {
+        fn decapsulate(&self, slot: &[u8]) -> Result<Secret> {
+            let mut rest = slot;
+            if take_field(&mut rest)? != self.name {
+                return Err(Error::AuthenticationFailed);
+            }
+            Ok(Secret::new(xor(self.name, rest)))
+        }
+
+        fn dem_open(&self, content_key: &Secret, ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+            let Some(split) = ciphertext.len().checked_sub(8) else {
+                return Err(Error::MalformedCiphertext("Missing tag".into()));
+            };
+            let (ciphertext, expected) = ciphertext.split_at(split);
+            if !ct_eq(&tag(&[content_key.as_slice(), aad, ciphertext]), expected) {
+                return Err(Error::AuthenticationFailed);
+            }
+            Ok(xor(content_key.as_slice(), ciphertext))
+        }
+    }
+
+    fn sender(max: u32) -> ToySender {
+        ToySender {
+            properties: SchemaBuilder::new().public_private_key_pair().multi_recipient(max).build(),
+        }
+    }
+
+    #[test]
+    fn every_recipient_can_open_and_others_cannot() {
+        let (alice, bob, carol) = (
+            ToyRecipient { name: b"alice" },
+            ToyRecipient { name: b"bob" },
+            ToyRecipient { name: b"carol" },
+        );
+
+        let sealed = sender(4).seal_multi(&[alice.name, bob.name], b"payload", b"header").unwrap();
+
+        assert_eq!(MultiRecipientCiphertext::from_bytes(&sealed).unwrap().slots.len(), 2);
+        assert_eq!(alice.open(&sealed, b"header").unwrap(), b"payload");
+        assert_eq!(bob.open(&sealed, b"header").unwrap(), b"payload");
+        assert!(matches!(carol.open(&sealed, b"header"), Err(Error::AuthenticationFailed)));
+        assert!(matches!(alice.open(&sealed, b"other"), Err(Error::AuthenticationFailed)));
+    }
+
+    #[test]
+    fn single_recipient_seal_opens() {
+        let alice = ToyRecipient { name: b"alice" };
+
+        let sealed = sender(1).seal(alice.name, b"payload", b"").unwrap();
+
+        assert_eq!(alice.open(&sealed, b"").unwrap(), b"payload");
+    }
+
+    #[test]
+    fn seal_multi_enforces_recipient_limit() {
+        let recipients: [&[u8]; 3] = [b"alice", b"bob", b"carol"];
+        let single =
+            ToySender { properties: SchemaBuilder::new().public_private_key_pair().build() };
+
+        assert!(matches!(
+            sender(2).seal_multi(&recipients, b"payload", b""),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            sender(2).seal_multi(&[], b"payload", b""),
+            Err(Error::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            single.seal_multi(&recipients[..1], b"payload", b""),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn seal_produces_a_single_slot_and_open_rejects_malformed_ciphertexts() {
+        let alice = ToyRecipient { name: b"alice" };
+
+        let sealed = sender(1).seal(alice.name, b"payload", b"header").unwrap();
+
+        let decoded = MultiRecipientCiphertext::from_bytes(&sealed).unwrap();
+        assert_eq!(decoded.slots.len(), 1);
+        assert_eq!(decoded.to_bytes().unwrap(), sealed);
+        assert!(matches!(alice.open(&sealed[..2], b"header"), Err(Error::MalformedCiphertext(_))));
+        assert!(matches!(alice.open(&sealed[..6], b"header"), Err(Error::MalformedCiphertext(_))));
+    }
+}
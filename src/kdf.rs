@@ -1 +1,286 @@
+use alloc::vec::Vec;
 
+use crate::{
+    CryptographicPrimitive,
+    ct::ct_eq,
+    error::{Error, Result},
+    secret::Secret,
+};
+
+/// The length of the PRK derived by the default [`Kdf::extract`], enough for 256-bit security.
+const DEFAULT_PRK_LEN: usize = 32;
+
+/// A key derivation function, such as HKDF, PBKDF2 or Argon2, which derives output key material
+/// from input key material (IKM), an optional salt and context information.  Derived key material
+/// that is held in memory, such as the output of [`Kdf::extract`], should be kept in a [`Secret`]
+/// so that it is wiped after use.
+pub trait Kdf: CryptographicPrimitive {
+    /// Returns the largest number of bytes that can be derived in one call, e.g. 255 times the
+    /// hash length for HKDF.
+    fn max_output_len(&self) -> usize;
+
+    /// Fills `out` with key material derived from `ikm`, `salt` and `info`.  Fails with
+    /// [`Error::InternalError`] if `out` is longer than `max_output_len()`; implementations can
+    /// check this with [`Kdf::check_output_len`].
+    fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out: &mut [u8]) -> Result<()>;
+
+    /// Fails with [`Error::InternalError`] if `len` bytes can't be derived in one call.
+    fn check_output_len(&self, len: usize) -> Result<()> {
+        if len > self.max_output_len() {
+            return Err(Error::InternalError(format!(
+                "Requested {} bytes of output, but at most {} can be derived",
+                len,
+                self.max_output_len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// The extract phase of an extract-then-expand KDF such as HKDF: concentrates the entropy of
+    /// `ikm` into a uniformly random pseudorandom key (PRK), which [`Kdf::expand`] can then
+    /// expand with any number of `info` values.  By default this derives a PRK of 32 bytes, or
+    /// `max_output_len()` if that is shorter, from `ikm` and `salt` with no `info`, so that the
+    /// two phases can be used with any KDF, e.g. a password-based one, whose cost is then paid
+    /// once rather than for each expansion.
+    fn extract(&self, salt: &[u8], ikm: &[u8]) -> Result<Secret> {
+        let mut prk = vec![0; self.max_output_len().min(DEFAULT_PRK_LEN)];
+        let result = self.derive(ikm, salt, &[], &mut prk);
+        let prk = Secret::new(prk);
+        result?;
+        Ok(prk)
+    }
+
+    /// The expand phase of an extract-then-expand KDF: fills `out` with key material derived
+    /// from `prk`, which must be uniformly random, and `info`.  By default this derives with
+    /// `prk` as the IKM and no salt; KDFs with a distinct expand phase, such as HKDF, must
+    /// override both this and [`Kdf::extract`].
+    fn expand(&self, prk: &[u8], info: &[u8], out: &mut [u8]) -> Result<()> {
+        self.derive(prk, &[], info, out)
+    }
+}
+
+/// The tunable costs of a password-based KDF such as Argon2id or scrypt, which make each guess
+/// expensive for an attacker.  See [`PasswordKdf`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CostParameters {
+    /// The memory used per hash, in KiB.
+    pub memory_kib: u32,
+    /// The number of passes, or iterations, over the memory.
+    pub iterations: u32,
+    /// The number of lanes computed in parallel.
+    pub parallelism: u32,
+}
+
+impl CostParameters {
+    /// Creates cost parameters, failing with [`Error::InvalidParameter`] if any is zero.
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self> {
+        let params = Self { memory_kib, iterations, parallelism };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Fails with [`Error::InvalidParameter`] if any of the parameters is zero.  The fields are
+    /// public, so parameters built without [`CostParameters::new`], e.g. deserialized alongside a
+    /// stored hash, may not have been checked.
+    pub fn validate(&self) -> Result<()> {
+        if self.memory_kib == 0 || self.iterations == 0 || self.parallelism == 0 {
+            return Err(Error::InvalidParameter(format!(
+                "Cost parameters must be non-zero, got {} KiB, {} iterations, parallelism {}",
+                self.memory_kib, self.iterations, self.parallelism
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The OWASP Password Storage Cheat Sheet's recommended minimum for Argon2id: 19 MiB of memory,
+/// 2 iterations and a parallelism of 1.
+impl Default for CostParameters {
+    fn default() -> Self {
+        Self { memory_kib: 19 * 1024, iterations: 2, parallelism: 1 }
+    }
+}
+
+/// A password-based KDF, such as Argon2id or scrypt, whose cost can be tuned to the hardware so
+/// that brute-forcing low-entropy passwords is expensive.  Memory-hard KDFs should report their
+/// costs in [`crate::runes::Rune::MemoryHardness`].
+pub trait PasswordKdf: Kdf {
+    /// Returns the cost parameters used by [`Kdf::derive`].
+    fn cost_parameters(&self) -> CostParameters;
+
+    /// Hashes `password` with `salt` at the specified cost, returning a hash suitable for
+    /// storage alongside the salt and parameters.  Fails with [`Error::InvalidParameter`] if any
+    /// of the parameters is zero; see [`CostParameters::validate`].
+    fn hash_password(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+        params: CostParameters,
+    ) -> Result<Vec<u8>> {
+        params.validate()?;
+        self.hash_password_unchecked(password, salt, params)
+    }
+
+    /// Hashes `password` as for [`PasswordKdf::hash_password`], which has already checked that
+    /// `params` are valid.  Callers should use `hash_password` instead.
+    fn hash_password_unchecked(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+        params: CostParameters,
+    ) -> Result<Vec<u8>>;
+
+    /// Checks that `password` hashes to `expected` with `salt` and `params`, comparing in
+    /// constant time.  Fails as [`PasswordKdf::hash_password`] does.  See
+    /// [`crate::ct::ct_eq`].
+    fn verify_password(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+        params: CostParameters,
+        expected: &[u8],
+    ) -> Result<bool> {
+        Ok(ct_eq(&self.hash_password(password, salt, params)?, expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runes::{Schema, SchemaBuilder};
+    use core::cell::Cell;
+
+    /// A toy KDF without a separate extract phase, deriving output from a non-cryptographic
+    /// FNV-1a hash of its inputs, of at most 32 bytes.
+    struct ToyKdf;
+
+    impl CryptographicPrimitive for ToyKdf {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new().security_bits(32).build()
+        }
+    }
+
+    impl Kdf for ToyKdf {
+        fn max_output_len(&self) -> usize {
+            32
+        }
+
+        fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out: &mut [u8]) -> Result<()> {
+            self.check_output_len(out.len())?;
+            let mut hash = 0xcbf29ce484222325u64;
+            for byte in [ikm, &[0xff], salt, &[0xff], info].concat() {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3);
+            }
+            for byte in out {
+                hash = (hash ^ (hash >> 32)).wrapping_mul(0x100000001b3);
+                *byte = (hash >> 32) as u8;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn single_phase_kdfs_expand_by_deriving() {
+        let mut derived = [0; 32];
+        ToyKdf.derive(b"prk", b"", b"info", &mut derived).unwrap();
+        let mut expanded = [0; 32];
+        ToyKdf.expand(b"prk", b"info", &mut expanded).unwrap();
+
+        assert_eq!(expanded, derived);
+        assert!(ToyKdf.check_output_len(32).is_ok());
+        assert!(matches!(
+            ToyKdf.expand(b"prk", b"info", &mut [0; 33]),
+            Err(Error::InternalError(_))
+        ));
+    }
+
+    #[test]
+    fn extract_then_expand_chains_to_derive() {
+        let prk = ToyKdf.extract(b"salt", b"ikm").unwrap();
+        let mut expected_prk = [0; 32];
+        ToyKdf.derive(b"ikm", b"salt", b"", &mut expected_prk).unwrap();
+        assert_eq!(prk.as_slice(), expected_prk);
+
+        let expand = |info: &[u8]| {
+            let mut okm = [0; 16];
+            ToyKdf.expand(prk.as_slice(), info, &mut okm).unwrap();
+            okm
+        };
+        let mut expected = [0; 16];
+        ToyKdf.derive(prk.as_slice(), b"", b"key 1", &mut expected).unwrap();
+        assert_eq!(expand(b"key 1"), expected);
+        assert_ne!(expand(b"key 2"), expected);
+        assert_ne!(ToyKdf.extract(b"pepper", b"ikm").unwrap().as_slice(), prk.as_slice());
+    }
+
+    /// A toy [`PasswordKdf`] that hashes with [`ToyKdf`], ignoring the costs, and counts the
+    /// hashes computed.
+    #[derive(Default)]
+    struct ToyPasswordKdf {
+        hashes: Cell<usize>,
+    }
+
+    impl CryptographicPrimitive for ToyPasswordKdf {
+        fn security_properties(&self) -> Schema {
+            ToyKdf.security_properties()
+        }
+    }
+
+    impl Kdf for ToyPasswordKdf {
+        fn max_output_len(&self) -> usize {
+            ToyKdf.max_output_len()
+        }
+
+        fn derive(&self, ikm: &[u8], salt: &[u8], info: &[u8], out: &mut [u8]) -> Result<()> {
+            ToyKdf.derive(ikm, salt, info, out)
+        }
+    }
+
+    impl PasswordKdf for ToyPasswordKdf {
+        fn cost_parameters(&self) -> CostParameters {
+            CostParameters::default()
+        }
+
+        fn hash_password_unchecked(
+            &self,
+            password: &[u8],
+            salt: &[u8],
+            _params: CostParameters,
+        ) -> Result<Vec<u8>> {
+            self.hashes.set(self.hashes.get() + 1);
+            let mut hash = vec![0; 16];
+            self.derive(password, salt, b"password", &mut hash)?;
+            Ok(hash)
+        }
+    }
+
+    #[test]
+    fn zero_cost_parameters_are_rejected() {
+        let kdf = ToyPasswordKdf::default();
+        let params = CostParameters::default();
+        let hash = kdf.hash_password(b"hunter2", b"salt", params).unwrap();
+
+        assert!(kdf.verify_password(b"hunter2", b"salt", params, &hash).unwrap());
+        assert!(!kdf.verify_password(b"hunter3", b"salt", params, &hash).unwrap());
+        assert_eq!(kdf.hashes.get(), 3);
+        assert!(matches!(CostParameters::new(0, 2, 1), Err(Error::InvalidParameter(_))));
+        for zeroed in [
+            CostParameters { memory_kib: 0, ..params },
+            CostParameters { iterations: 0, ..params },
+            CostParameters { parallelism: 0, ..params },
+        ] {
+            assert!(matches!(zeroed.validate(), Err(Error::InvalidParameter(_))));
+            assert!(matches!(
+                kdf.hash_password(b"hunter2", b"salt", zeroed),
+                Err(Error::InvalidParameter(_))
+            ));
+            assert!(matches!(
+                kdf.verify_password(b"hunter2", b"salt", zeroed, &hash),
+                Err(Error::InvalidParameter(_))
+            ));
+        }
+        assert_eq!(kdf.hashes.get(), 3);
+    }
+}
@@ -1 +1,131 @@
+use alloc::vec::Vec;
 
+use crate::{CryptographicPrimitive, error::Result, secret::Secret};
+
+/// The raw output of a key agreement, wiped from memory when dropped.
+pub type SharedSecret = Secret;
+
+/// A Diffie-Hellman style key agreement, such as ECDH or X25519, holding a private key that is
+/// either ephemeral, used for a single agreement to provide forward secrecy, or static, used
+/// for many.
+///
+/// The shared secret is not uniformly random, so it must not be used directly as a key.  Run it
+/// through a KDF, such as HKDF (see [`crate::kdf::Kdf`]), together with context binding it to the
+/// parties and the protocol.
+pub trait KeyAgreement: CryptographicPrimitive {
+    /// Returns the encoded public key to send to the peer.
+    fn public_key(&self) -> Vec<u8>;
+
+    /// Computes the secret shared with the peer whose public key is `peer_public`.  Fails with
+    /// [`crate::error::Error::InvalidKeyMaterial`] if `peer_public` is not a valid encoding, or,
+    /// if [`KeyAgreement::contributory_behavior`] is true, if it is a low-order or identity
+    /// point.
+    fn agree(&self, peer_public: &[u8]) -> Result<SharedSecret>;
+
+    /// Returns true if the implementation rejects peer public keys that would force the shared
+    /// secret to a value independent of its own private key, such as low-order points in X25519,
+    /// so that both parties contribute to the secret.  Implementations that do should report
+    /// [`crate::runes::Rune::ContributoryKeyAgreement`] from `security_properties()`.
+    fn contributory_behavior(&self) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Error,
+        runes::{Rune, Schema, SchemaBuilder},
+    };
+
+    /// The prime modulus of the toy group, 2³¹ - 1, of which 7 is a generator.
+    const P: u64 = 0x7fffffff;
+    const G: u64 = 7;
+
+    /// A toy Diffie-Hellman key agreement in the multiplicative group modulo [`P`], with public
+    /// keys encoded as 8 big-endian bytes.  Its only small subgroups are those of the identity,
+    /// 1, and of `P - 1`, of order 2.
+    struct ToyDh {
+        private: u64,
+        contributory: bool,
+    }
+
+    fn pow(mut base: u64, mut exponent: u64) -> u64 {
+        let mut result = 1;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base % P;
+            }
+            base = base * base % P;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    impl CryptographicPrimitive for ToyDh {
+        fn security_properties(&self) -> Schema {
+            let builder = SchemaBuilder::new().security_bits(15);
+            if self.contributory { builder.contributory_key_agreement() } else { builder }.build()
+        }
+    }
+
+    impl KeyAgreement for ToyDh {
+        fn public_key(&self) -> Vec<u8> {
+            pow(G, self.private).to_be_bytes().to_vec()
+        }
+
+        fn agree(&self, peer_public: &[u8]) -> Result<SharedSecret> {
+            let peer = match <[u8; 8]>::try_from(peer_public) {
+                Ok(bytes) => u64::from_be_bytes(bytes),
+                Err(_) => return Err(Error::InvalidKeyMaterial("Wrong public key length".into())),
+            };
+            if peer == 0 || peer >= P {
+                return Err(Error::InvalidKeyMaterial("Public key out of range".into()));
+            }
+            if self.contributory && (peer == 1 || peer == P - 1) {
+                return Err(Error::InvalidKeyMaterial("Public key of low order".into()));
+            }
+            Ok(SharedSecret::new(pow(peer, self.private).to_be_bytes().to_vec()))
+        }
+
+        fn contributory_behavior(&self) -> bool {
+            self.contributory
+        }
+    }
+
+    #[test]
+    fn both_parties_agree_on_the_secret() {
+        let alice = ToyDh { private: 123456789, contributory: true };
+        let bob = ToyDh { private: 987654321, contributory: false };
+
+        let secret = alice.agree(&bob.public_key()).unwrap();
+        assert_eq!(secret.as_slice(), bob.agree(&alice.public_key()).unwrap().as_slice());
+        assert_ne!(secret.as_slice(), alice.public_key());
+        assert!(matches!(alice.agree(&[1; 4]), Err(Error::InvalidKeyMaterial(_))));
+        assert!(matches!(alice.agree(&P.to_be_bytes()), Err(Error::InvalidKeyMaterial(_))));
+    }
+
+    #[test]
+    fn contributory_agreements_reject_low_order_public_keys() {
+        let contributory = ToyDh { private: 123456789, contributory: true };
+        let non_contributory = ToyDh { private: 123456789, contributory: false };
+
+        for low_order in [1, P - 1] {
+            let public = low_order.to_be_bytes();
+            assert!(matches!(contributory.agree(&public), Err(Error::InvalidKeyMaterial(_))));
+            assert!(non_contributory.agree(&public).is_ok());
+        }
+        let forced = |private| ToyDh { private, contributory: false }.agree(&1u64.to_be_bytes());
+        assert_eq!(forced(2).unwrap().as_slice(), forced(3).unwrap().as_slice());
+    }
+
+    #[test]
+    fn contributory_behavior_is_reported_as_a_rune() {
+        for contributory in [true, false] {
+            let dh = ToyDh { private: 5, contributory };
+            let reported =
+                dh.security_properties().runes().contains(&Rune::ContributoryKeyAgreement);
+            assert_eq!(reported, dh.contributory_behavior());
+            assert_eq!(reported, contributory);
+        }
+    }
+}
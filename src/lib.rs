@@ -2,6 +2,8 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod ct;
+mod encoding;
 pub mod error;
 mod platform;
 
@@ -30,10 +32,20 @@ pub mod sponge_function;
 
 pub mod runes;
 
+pub mod secret;
+
 pub mod construction;
 
 pub mod provider;
 
+pub mod policy;
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 pub trait CryptographicPrimitive {
     fn security_properties(&self) -> runes::Schema;
 }
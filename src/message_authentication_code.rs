@@ -1 +1,91 @@
+use alloc::vec::Vec;
 
+use crate::{CryptographicPrimitive, ct::ct_eq, error::Result};
+
+/// A keyed message authentication code, such as HMAC, CMAC or KMAC, in the middle of computing a
+/// tag.  The key is supplied when the MAC is created; the message is then supplied in chunks with
+/// [`Mac::update`], and the computation ends with [`Mac::finalize`] or [`Mac::verify`].
+///
+/// `security_properties()` should report
+/// [`crate::runes::SoftwareSideChannelResistance::ConstantTime`] if the tag computation is
+/// constant time.  The comparison in [`Mac::verify`] always is.
+pub trait Mac: CryptographicPrimitive + Sized {
+    /// Returns the length of the tag, in bytes.
+    fn tag_size(&self) -> usize;
+
+    /// Appends `data` to the message.
+    fn update(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Returns the tag of the message, of `tag_size()` bytes.
+    fn finalize(self) -> Result<Vec<u8>>;
+
+    /// Checks that `expected` is the tag of the message.  The comparison takes the same time
+    /// wherever the tags first differ, rather than stopping at the first differing byte, which
+    /// would let an attacker who can time verification forge a tag byte by byte.  Only whether
+    /// the lengths match may be revealed.  See [`crate::ct::ct_eq`].
+    fn verify(self, expected: &[u8]) -> Result<bool> {
+        Ok(ct_eq(&self.finalize()?, expected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runes::{Schema, SchemaBuilder, SoftwareSideChannelResistance};
+
+    /// A toy MAC whose 16-byte tag is a non-cryptographic FNV-1a mix of the key and message.
+    struct ToyMac {
+        key: u8,
+        message: Vec<u8>,
+    }
+
+    impl CryptographicPrimitive for ToyMac {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new()
+                .security_bits(8)
+                .software_side_channel_resistance(SoftwareSideChannelResistance::ConstantTime)
+                .build()
+        }
+    }
+
+    impl Mac for ToyMac {
+        fn tag_size(&self) -> usize {
+            16
+        }
+
+        fn update(&mut self, data: &[u8]) -> Result<()> {
+            self.message.extend_from_slice(data);
+            Ok(())
+        }
+
+        fn finalize(self) -> Result<Vec<u8>> {
+            let mut hash = 0xcbf29ce484222325u64;
+            for byte in [&[self.key], self.message.as_slice()].concat() {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3);
+            }
+            Ok((0..16).map(|i| ((hash ^ i).wrapping_mul(0x100000001b3) >> 56) as u8).collect())
+        }
+    }
+
+    fn mac(message: &[u8]) -> ToyMac {
+        ToyMac { key: 42, message: message.to_vec() }
+    }
+
+    #[test]
+    fn verify_accepts_only_the_exact_tag() {
+        let tag = mac(b"message").finalize().unwrap();
+        let differing = |index: usize| {
+            let mut tag = tag.clone();
+            tag[index] ^= 0x01;
+            tag
+        };
+
+        assert_eq!(tag.len(), mac(b"").tag_size());
+        assert!(mac(b"message").verify(&tag).unwrap());
+        assert!(!mac(b"message").verify(&differing(0)).unwrap());
+        assert!(!mac(b"message").verify(&differing(tag.len() - 1)).unwrap());
+        assert!(!mac(b"message").verify(&tag[..tag.len() - 1]).unwrap());
+        assert!(!mac(b"message").verify(&[tag.as_slice(), &[0]].concat()).unwrap());
+        assert!(!mac(b"massage").verify(&tag).unwrap());
+    }
+}
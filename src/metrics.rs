@@ -0,0 +1,214 @@
+//! Aggregate counters of forge and seal outcomes, for operators who want to monitor key usage
+//! without instrumenting every call site.  This module is only available with the `metrics`
+//! feature.
+//!
+//! The default implementations of the provider methods report events themselves:
+//!
+//! - [`crate::provider::Provider::forge`] reports the construction of each key it forges to the
+//!   sink of the provider's registry, obtained with
+//!   [`crate::construction::ConstructionRegistry::metrics_sink`].
+//! - [`crate::provider::BindRune::seal_envelope`], [`crate::provider::BindRune::unseal_envelope`]
+//!   and [`crate::provider::Provider::forge_and_seal`] report seal successes, exceeded limits and
+//!   authentication failures to [`crate::provider::BindRune::metrics_sink`], which is usually the
+//!   same sink.
+//!
+//! Providers that override those methods, or whose callers use [`crate::provider::BindRune::seal`]
+//! and [`crate::provider::BindRune::unseal`] directly, must report the events themselves.
+
+use crate::{
+    construction::ConstructionIdentifier,
+    error::{Error, Result},
+};
+
+/// Receives metrics events.  Every method has a no-op default, so sinks need only implement the
+/// events they count.  Methods take `&self` and may be called frequently, so implementations
+/// should use interior mutability and do as little work as possible.
+pub trait MetricsSink {
+    /// A construction was selected to satisfy a forge request.
+    fn construction_selected(&self, _construction: &ConstructionIdentifier) {}
+
+    /// A seal operation completed successfully.
+    fn seal_succeeded(&self) {}
+
+    /// An operation was refused because it would exceed a limit of the
+    /// [`crate::provider::BindRune`], such as [`Error::MessageTooLong`] or
+    /// [`Error::TotalDataTooLong`].
+    fn limit_exceeded(&self, _error: &Error) {}
+
+    /// An unseal operation failed with [`Error::AuthenticationFailed`].
+    fn unseal_auth_failed(&self) {}
+}
+
+/// A [`MetricsSink`] that discards all events.  This is the default sink.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopSink;
+
+impl MetricsSink for NoopSink {}
+
+/// Reports the outcome of a seal operation to `sink`, if any.
+pub(crate) fn report_seal<T>(sink: Option<&dyn MetricsSink>, outcome: &Result<T>) {
+    match (sink, outcome) {
+        (Some(sink), Ok(_)) => sink.seal_succeeded(),
+        (Some(sink), Err(error)) => report_limit(sink, error),
+        (None, _) => {}
+    }
+}
+
+/// Reports the outcome of an unseal operation to `sink`, if any.
+pub(crate) fn report_unseal<T>(sink: Option<&dyn MetricsSink>, outcome: &Result<T>) {
+    match (sink, outcome) {
+        (Some(sink), Err(Error::AuthenticationFailed)) => sink.unseal_auth_failed(),
+        (Some(sink), Err(error)) => report_limit(sink, error),
+        _ => {}
+    }
+}
+
+fn report_limit(sink: &dyn MetricsSink, error: &Error) {
+    if matches!(error, Error::MessageTooLong(_) | Error::TotalDataTooLong(_)) {
+        sink.limit_exceeded(error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        construction::{
+            Construction, ConstructionRegistry,
+            tests::{TestConstruction, registry, schema_with_bits},
+        },
+        provider::{BindRune, Operation, Provider, tests::ToyAeadBindRune},
+        runes::Schema,
+    };
+    use alloc::{boxed::Box, rc::Rc, string::String, vec::Vec};
+    use core::cell::RefCell;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Event {
+        Selected(String),
+        Sealed,
+        LimitExceeded,
+        AuthFailed,
+    }
+
+    /// A sink that records events in a log shared by its clones.
+    #[derive(Clone, Default)]
+    struct RecordingSink {
+        events: Rc<RefCell<Vec<Event>>>,
+    }
+
+    impl MetricsSink for RecordingSink {
+        fn construction_selected(&self, construction: &ConstructionIdentifier) {
+            self.events.borrow_mut().push(Event::Selected(construction.as_str().into()));
+        }
+
+        fn seal_succeeded(&self) {
+            self.events.borrow_mut().push(Event::Sealed);
+        }
+
+        fn limit_exceeded(&self, _error: &Error) {
+            self.events.borrow_mut().push(Event::LimitExceeded);
+        }
+
+        fn unseal_auth_failed(&self) {
+            self.events.borrow_mut().push(Event::AuthFailed);
+        }
+    }
+
+    struct MeteredBindRune {
+        inner: ToyAeadBindRune,
+        sink: RecordingSink,
+    }
+
+    impl BindRune for MeteredBindRune {
+        fn seal(&self) -> Result<Box<dyn Operation>> {
+            self.inner.seal()
+        }
+
+        fn unseal(&self) -> Result<Box<dyn Operation>> {
+            self.inner.unseal()
+        }
+
+        fn schema(&self) -> Schema {
+            self.inner.schema()
+        }
+
+        fn metrics_sink(&self) -> Option<&dyn MetricsSink> {
+            Some(&self.sink)
+        }
+    }
+
+    struct MeteredProvider {
+        registry: ConstructionRegistry,
+        sink: RecordingSink,
+    }
+
+    impl Provider for MeteredProvider {
+        fn construction_registry(&self) -> Option<&ConstructionRegistry> {
+            Some(&self.registry)
+        }
+
+        fn forge_construction(
+            &self,
+            _label: &str,
+            construction: &dyn Construction,
+            _desired_properties: Schema,
+        ) -> Result<Box<dyn BindRune>> {
+            Ok(Box::new(MeteredBindRune {
+                inner: ToyAeadBindRune { schema: construction.schema() },
+                sink: self.sink.clone(),
+            }))
+        }
+
+        fn forge_shareable(&self, _: &str, _: u128, _: Schema) -> Result<Box<dyn BindRune>> {
+            Err(Error::Unsupported("Shareable keys are not supported".into()))
+        }
+
+        fn retrieve(&self, _label: &str) -> Result<Box<dyn BindRune>> {
+            Err(Error::UnknownLabel)
+        }
+    }
+
+    #[test]
+    fn forge_seal_and_failed_unseal_are_counted() {
+        let sink = RecordingSink::default();
+        let mut registry = registry([TestConstruction::new("aes-128", schema_with_bits(128))]);
+        registry.set_metrics_sink(Box::new(sink.clone()));
+        let provider = MeteredProvider { registry, sink: sink.clone() };
+
+        let bind_rune = provider.forge("key", schema_with_bits(128)).unwrap();
+        let mut envelope = bind_rune.seal_envelope(b"message", b"").unwrap();
+        *envelope.last_mut().unwrap() ^= 1;
+        let unsealed = bind_rune.unseal_envelope(&envelope, b"");
+
+        assert!(matches!(unsealed, Err(Error::AuthenticationFailed)));
+        assert_eq!(
+            *sink.events.borrow(),
+            [Event::Selected("aes-128".into()), Event::Sealed, Event::AuthFailed]
+        );
+    }
+
+    #[test]
+    fn selection_queries_are_not_counted() {
+        let sink = RecordingSink::default();
+        let mut registry = registry([TestConstruction::new("aes-128", schema_with_bits(128))]);
+        registry.set_metrics_sink(Box::new(sink.clone()));
+
+        assert!(registry.best_match(&schema_with_bits(128)).is_some());
+        assert!(registry.select(&schema_with_bits(128)).is_ok());
+
+        assert!(sink.events.borrow().is_empty());
+    }
+
+    #[test]
+    fn only_limit_errors_count_as_exceeded_limits() {
+        let sink = RecordingSink::default();
+
+        report_seal::<()>(Some(&sink), &Err(Error::TotalDataTooLong("budget".into())));
+        report_seal::<()>(Some(&sink), &Err(Error::KeyUsageViolation("sign only".into())));
+        report_unseal::<()>(Some(&sink), &Err(Error::MessageTooLong("too long".into())));
+        report_seal(None, &Ok(()));
+
+        assert_eq!(*sink.events.borrow(), [Event::LimitExceeded, Event::LimitExceeded]);
+    }
+}
@@ -0,0 +1,187 @@
+//! Enforcement of organizational policy on top of any [`Provider`].
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    construction::ConstructionParams,
+    error::Result,
+    provider::{BindRune, CounterState, OperationResult, Provider},
+    runes::Schema,
+};
+
+/// A [`Provider`] that guarantees no key is forged or imported below a baseline, regardless of
+/// what application code requests.  The baseline is merged into every request with
+/// [`Schema::merge`] before the request is passed to the wrapped provider, so requests can only
+/// be strengthened, never weakened.  A request that conflicts irreconcilably with the baseline,
+/// for example by requiring a different escrow policy, is rejected with
+/// [`crate::error::Error::ConflictingRequirements`].
+///
+/// Because requests are strengthened, a request that the wrapped provider could satisfy on its
+/// own may be rejected with [`crate::error::Error::UnsatisfiableRequirements`] if the baseline
+/// exceeds what the provider can deliver.  The schema in the error reflects the merged request.
+pub struct PolicyEnforcingProvider<P: Provider> {
+    inner: P,
+    baseline: Schema,
+}
+
+impl<P: Provider> PolicyEnforcingProvider<P> {
+    pub fn new(inner: P, baseline: Schema) -> Self {
+        Self { inner, baseline }
+    }
+
+    pub fn baseline(&self) -> &Schema {
+        &self.baseline
+    }
+
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P: Provider> Provider for PolicyEnforcingProvider<P> {
+    fn forge(&self, label: &str, desired_properties: Schema) -> Result<Box<dyn BindRune>> {
+        self.inner.forge(label, self.baseline.merge(&desired_properties)?)
+    }
+
+    /// Lists the capabilities of the wrapped provider that meet the baseline.
+    fn capabilities(&self) -> Vec<Schema> {
+        let mut capabilities = self.inner.capabilities();
+        capabilities.retain(|schema| schema.satisfies(&self.baseline));
+        capabilities
+    }
+
+    fn can_forge(&self, requirements: &Schema) -> bool {
+        self.baseline.merge(requirements).is_ok_and(|merged| self.inner.can_forge(&merged))
+    }
+
+    fn forge_shareable(
+        &self,
+        label: &str,
+        uuid: u128,
+        desired_properties: Schema,
+    ) -> Result<Box<dyn BindRune>> {
+        self.inner.forge_shareable(label, uuid, self.baseline.merge(&desired_properties)?)
+    }
+
+    fn retrieve(&self, label: &str) -> Result<Box<dyn BindRune>> {
+        self.inner.retrieve(label)
+    }
+
+    fn import_raw(
+        &self,
+        label: &str,
+        key_material: &[u8],
+        construction: &ConstructionParams,
+        requirements: &Schema,
+    ) -> Result<Box<dyn BindRune>> {
+        self.inner.import_raw(
+            label,
+            key_material,
+            construction,
+            &self.baseline.merge(requirements)?,
+        )
+    }
+
+    fn forge_and_seal(
+        &self,
+        label: &str,
+        requirements: &Schema,
+        plaintext: &[u8],
+    ) -> Result<(Box<dyn BindRune>, OperationResult)> {
+        self.inner.forge_and_seal(label, &self.baseline.merge(requirements)?, plaintext)
+    }
+
+    fn restore_counters(&self, label: &str, state: CounterState) -> Result<Box<dyn BindRune>> {
+        self.inner.restore_counters(label, state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        construction::ConstructionIdentifier,
+        construction::tests::{TestConstruction, registry, schema_with_bits},
+        error::Error,
+        provider::tests::RegistryProvider,
+        runes::{EscrowPolicy, SchemaBuilder},
+        testing::{MockProvider, ProviderCall},
+    };
+
+    fn policy_provider() -> PolicyEnforcingProvider<MockProvider> {
+        let baseline =
+            SchemaBuilder::new().security_bits(192).escrow(EscrowPolicy::Forbidden).build();
+        PolicyEnforcingProvider::new(MockProvider::new(), baseline)
+    }
+
+    #[test]
+    fn requests_are_strengthened_to_the_baseline() {
+        let provider = policy_provider();
+        let request = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+        let merged = SchemaBuilder::new()
+            .security_bits(192)
+            .quantum_resistance(true)
+            .escrow(EscrowPolicy::Forbidden)
+            .build();
+
+        let bind_rune = provider.forge("key", request.clone()).unwrap();
+        let params = ConstructionParams::new(ConstructionIdentifier::new("aes-256"));
+        provider.import_raw("imported", &[0; 32], &params, &request).unwrap();
+
+        assert_eq!(bind_rune.schema(), merged);
+        provider.inner().assert_called(&ProviderCall::Forge {
+            label: "key".into(),
+            desired_properties: merged.clone(),
+        });
+        provider.inner().assert_called(&ProviderCall::ImportRaw {
+            label: "imported".into(),
+            construction: params,
+            requirements: merged,
+        });
+    }
+
+    #[test]
+    fn requests_conflicting_with_the_baseline_are_rejected() {
+        let provider = policy_provider();
+        let request = SchemaBuilder::new().escrow(EscrowPolicy::Required).build();
+
+        assert!(matches!(
+            provider.forge("key", request.clone()),
+            Err(Error::ConflictingRequirements(_))
+        ));
+        assert!(matches!(
+            provider.forge_and_seal("key", &request, b"data"),
+            Err(Error::ConflictingRequirements(_))
+        ));
+        assert!(!provider.can_forge(&request));
+        provider.inner().assert_not_called();
+    }
+
+    #[test]
+    fn capabilities_below_the_baseline_are_hidden() {
+        let inner = RegistryProvider {
+            registry: registry([
+                TestConstruction::new("aes-256", schema_with_bits(255)),
+                TestConstruction::new("aes-128", schema_with_bits(128)),
+            ]),
+        };
+        assert_eq!(inner.capabilities(), [schema_with_bits(128), schema_with_bits(255)]);
+
+        let provider = PolicyEnforcingProvider::new(inner, schema_with_bits(192));
+        assert_eq!(provider.capabilities(), [schema_with_bits(255)]);
+        assert!(MockProvider::new().capabilities().is_empty());
+    }
+
+    #[test]
+    fn can_forge_checks_requests_strengthened_to_the_baseline() {
+        let inner = RegistryProvider {
+            registry: registry([TestConstruction::new("aes-128", schema_with_bits(128))]),
+        };
+        assert!(inner.can_forge(&schema_with_bits(128)));
+        assert!(!inner.can_forge(&SchemaBuilder::new().quantum_resistance(true).build()));
+
+        let provider = PolicyEnforcingProvider::new(inner, schema_with_bits(192));
+        assert!(!provider.can_forge(&schema_with_bits(128)));
+        assert!(!MockProvider::new().can_forge(&schema_with_bits(128)));
+    }
+}
@@ -10,9 +10,18 @@
 //! - Providing a secure environment for the keys.
 //! - Providing a secure environment for the operations.
 
+use crate::construction::{
+    Construction, ConstructionIdentifier, ConstructionParams, ConstructionRegistry, Overhead,
+};
+use crate::encoding::{hex_decode, hex_encode, put_field, take, take_field};
 use crate::error::{Error, Result};
-use crate::runes::Schema;
-use alloc::{boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "metrics")]
+use crate::metrics::{MetricsSink, report_seal, report_unseal};
+use crate::runes::{EscrowPolicy, KeyUsage, Rune, Schema, SchemaBuilder};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, str::FromStr};
+use jiff::Zoned;
+use zeroize::Zeroize;
 
 /// Many cryptographic operations require a variation parameter to be provided.  The variation
 /// parameter is a value that is used to vary the behavior of the operation.  Different operations
@@ -32,6 +41,8 @@ use alloc::{boxed::Box, string::String, vec::Vec};
 /// to an operation.  The [`VariationParameter`] struct is used to create variation parameters and
 /// is used to pass variation parameters to operations.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum VariationType {
     /// The variation is a value that is not guaranteed to be unique or unpredictable.  This type of
     /// variation is only useful for algorithms like AES-XTS that require tweaks, not nonces or IVs.
@@ -117,10 +128,50 @@ impl VariationParam {
     pub fn arbitrary(data: Vec<u8>) -> Self {
         Self { data, variation_type: VariationType::Arbitrary }
     }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn variation_type(&self) -> &VariationType {
+        &self.variation_type
+    }
+}
+
+/// Formats the variation as its type followed by its value in hex, e.g. `unique:000102`.  This
+/// form can be parsed back with [`str::parse`].
+impl fmt::Display for VariationParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let variation_type = match self.variation_type {
+            VariationType::Arbitrary => "arbitrary",
+            VariationType::Random => "random",
+            VariationType::Unique => "unique",
+        };
+        write!(f, "{}:{}", variation_type, hex_encode(&self.data))
+    }
+}
+
+impl FromStr for VariationParam {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || Error::VariationInvalid(format!("Cannot parse variation {:?}", text));
+        let (variation_type, data) = text.split_once(':').ok_or_else(invalid)?;
+        let data = hex_decode(data).ok_or_else(invalid)?;
+        match variation_type {
+            "arbitrary" => Ok(Self::arbitrary(data)),
+            "random" => Ok(Self::random(data)),
+            "unique" => Ok(Self::unique(data)),
+            _ => Err(invalid()),
+        }
+    }
 }
 
 type VariationSize = Option<u16>;
 
+/// The version of the envelope format produced by [`BindRune::seal_envelope`].
+pub const ENVELOPE_VERSION: u8 = 1;
+
 /// [`BindRune`] encapsulates cryptographic key material and a defined set of security properties.
 /// Applying a `BindRune` to data means applying the security properties of the `BindRune` to the data.
 /// This may include encrypting, authenticating, or otherwise protecting the data.
@@ -132,19 +183,353 @@ type VariationSize = Option<u16>;
 /// `BindRune`.
 ///
 /// [`BindRune`]s are created by the [`Provider::forge`] method.
+///
+/// Implementations that hold key material or key-dependent state in process memory, such as an
+/// expanded key schedule, must wipe it when the `BindRune` is dropped, for example by storing it
+/// in [`zeroize::Zeroizing`] or implementing [`Drop`] with [`zeroize::Zeroize`].  Implementations
+/// that only hold a handle to key material in an isolated environment have nothing to wipe.
 pub trait BindRune {
-    /// Seal data, meaning apply the security properties of the [`BindRune`] to it.
+    /// Seal data, meaning apply the security properties of the [`BindRune`] to it.  Fails with
+    /// [`Error::KeyUsageViolation`] unless the schema permits
+    /// [`crate::runes::KeyUsage::Encrypt`].
     fn seal(&self) -> Result<Box<dyn Operation>>;
 
     /// Unseal data, meaning remove the security properties of the [`BindRune`] from it.  This may
-    /// include validating integrity and/or authenticity of the data.
+    /// include validating integrity and/or authenticity of the data.  Fails with
+    /// [`Error::KeyUsageViolation`] unless the schema permits
+    /// [`crate::runes::KeyUsage::Decrypt`].
     fn unseal(&self) -> Result<Box<dyn Operation>>;
 
     /// Return the schema of the [`BindRune`].  The schema is a set of [`Rune`]s that specify the
     /// security properties of the [`BindRune`].
     fn schema(&self) -> Schema;
+
+    /// Return the number of bytes [`BindRune::seal`] adds to the plaintext.  This is the
+    /// [`crate::construction::Construction::overhead`] of the construction used by the
+    /// [`BindRune`].
+    ///
+    /// The default implementation reports no overhead, which is only correct for `BindRune`s
+    /// whose seal output is exactly as long as the plaintext, such as those of unauthenticated
+    /// stream ciphers.  Other implementations must override it, or callers sizing buffers from it
+    /// will truncate sealed data.
+    fn overhead(&self) -> Overhead {
+        Overhead::default()
+    }
+
+    /// Check that the [`BindRune`]'s schema satisfies `original_request`, the schema it was forged
+    /// from, failing with [`Error::UnsatisfiableRequirements`] carrying the unmet runes (see
+    /// [`Schema::delta`]) if it doesn't.  Preferences are not checked.
+    ///
+    /// A correct provider never forges a `BindRune` that fails this check, but it is cheap, so
+    /// callers that don't fully trust the provider should run it immediately after forging, as a
+    /// defense in depth against provider bugs.  It should not be run later, because enforced
+    /// limits count down with use and will eventually fall below the requested limits.
+    fn verify_meets(&self, original_request: &Schema) -> Result<()> {
+        let schema = self.schema();
+        if schema.satisfies(original_request) {
+            Ok(())
+        } else {
+            Err(Error::UnsatisfiableRequirements(schema.delta(original_request)))
+        }
+    }
+
+    /// Return the identifier of the construction used by the [`BindRune`], if the provider
+    /// discloses it.  The default implementation returns `None`.
+    fn construction(&self) -> Option<ConstructionIdentifier> {
+        None
+    }
+
+    /// Return the sink to which the default implementations of [`BindRune::seal_envelope`],
+    /// [`BindRune::unseal_envelope`] and [`Provider::forge_and_seal`] report the outcomes of
+    /// operations, usually the [`crate::construction::ConstructionRegistry::metrics_sink`] of the
+    /// registry the `BindRune` was forged from.  Implementations that override those methods, or
+    /// that are used through [`BindRune::seal`] and [`BindRune::unseal`] directly, report to it
+    /// themselves.  The default implementation returns `None`, so nothing is reported.  Only
+    /// available with the `metrics` feature.
+    #[cfg(feature = "metrics")]
+    fn metrics_sink(&self) -> Option<&dyn MetricsSink> {
+        None
+    }
+
+    /// Derive a subkey from this [`BindRune`]'s key, satisfying `requirements`, and store it under
+    /// the specified label.  The subkey reports the
+    /// [`crate::runes::Rune::DerivationDepth`] computed by [`Schema::child_derivation_depth`],
+    /// which fails with [`Error::DerivationTooDeep`] if `requirements` caps the depth below it.
+    /// Derivation requires [`crate::runes::KeyUsage::Derive`].
+    ///
+    /// The default implementation performs those checks and then returns
+    /// [`Error::Unsupported`].  Implementations that override this method must perform them too.
+    fn derive_subkey(&self, _label: &str, requirements: &Schema) -> Result<Box<dyn BindRune>> {
+        let schema = self.schema();
+        schema.check_usage(KeyUsage::Derive)?;
+        schema.child_derivation_depth(requirements)?;
+        Err(Error::Unsupported("Subkey derivation is not supported".into()))
+    }
+
+    /// Return the public key of a [`BindRune`] that uses a public/private key pair, encoded as
+    /// a DER SubjectPublicKeyInfo.
+    ///
+    /// A `BindRune` whose schema [`Schema::is_symmetric_only`] has no public key, so the default
+    /// implementation fails with [`Error::KeyUsageViolation`] for such `BindRune`s, and with
+    /// [`Error::Unsupported`] otherwise.  Implementations that override this method must preserve
+    /// the former.
+    fn public_key(&self) -> Result<Vec<u8>> {
+        if self.schema().is_symmetric_only() {
+            return Err(Error::KeyUsageViolation("Symmetric keys have no public key".into()));
+        }
+        Err(Error::Unsupported("Public key export is not supported".into()))
+    }
+
+    /// Seal `plaintext` with associated data `aad` and return a single self-describing envelope
+    /// containing everything but the key and `aad` needed to unseal it with
+    /// [`BindRune::unseal_envelope`].  This is the simplest way to store encrypted data.  Only
+    /// [`VariationStrategy::Automatic`](crate::runes::VariationStrategy::Automatic) `BindRune`s
+    /// are supported, since the caller has no opportunity to provide a variation.
+    ///
+    /// The envelope layout is as follows, with all lengths big-endian 32-bit values:
+    ///
+    /// | Field          | Size       | Contents                                              |
+    /// |----------------|------------|-------------------------------------------------------|
+    /// | version        | 1 byte     | [`ENVELOPE_VERSION`]                                  |
+    /// | variation type | 1 byte     | 0 for none, else 1 + the [`VariationType`] ordinal    |
+    /// | construction   | 4 + length | the [`BindRune::construction`] identifier, or empty   |
+    /// | variation      | 4 + length | the variation used by the seal operation, or empty    |
+    /// | tag            | 4 + length | the authentication tag, or empty                      |
+    /// | ciphertext     | remainder  | the sealed data                                       |
+    ///
+    /// The version is incremented whenever the layout changes, and envelopes with an unknown
+    /// version are rejected rather than misinterpreted.
+    fn seal_envelope(&self, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let sealed: Result<_> = (|| {
+            let operation = self.seal()?;
+            operation.update_aad(aad)?;
+            operation.finish_aad()?;
+            let ciphertext = operation.update(plaintext)?;
+            Ok((ciphertext, operation.finish(&[])?))
+        })();
+        #[cfg(feature = "metrics")]
+        report_seal(self.metrics_sink(), &sealed);
+        let (mut ciphertext, mut result) = sealed?;
+        ciphertext.append(&mut result.data);
+
+        let mut variation = None;
+        let mut tag: &[u8] = &[];
+        for parameter in &result.output_parameters {
+            match parameter {
+                OutputParameter::Variation(param) => variation = Some(param),
+                OutputParameter::AuthenticationTag(bytes) => tag = bytes,
+            }
+        }
+        let construction = self.construction();
+
+        let mut envelope = vec![ENVELOPE_VERSION];
+        envelope.push(match variation {
+            None => 0,
+            Some(param) => match param.variation_type() {
+                VariationType::Arbitrary => 1,
+                VariationType::Random => 2,
+                VariationType::Unique => 3,
+            },
+        });
+        put_field(
+            &mut envelope,
+            construction.as_ref().map_or(&[][..], |identifier| identifier.as_str().as_bytes()),
+        )?;
+        put_field(&mut envelope, variation.map_or(&[][..], VariationParam::data))?;
+        put_field(&mut envelope, tag)?;
+        envelope.extend_from_slice(&ciphertext);
+        Ok(envelope)
+    }
+
+    /// Unseal an envelope produced by [`BindRune::seal_envelope`] with the same associated data
+    /// `aad`.  Fails with [`Error::MalformedCiphertext`] if the envelope is truncated, has an
+    /// unknown version, or names a different construction than this [`BindRune`].
+    ///
+    /// The tag, if any, is passed to the unseal operation's [`Operation::finish`] after the
+    /// ciphertext has been passed to [`Operation::update`].
+    fn unseal_envelope(&self, envelope: &[u8], aad: &[u8]) -> Result<OperationResult> {
+        let mut rest = envelope;
+        let header = take(&mut rest, 2)?;
+        if header[0] != ENVELOPE_VERSION {
+            return Err(Error::MalformedCiphertext(format!(
+                "Unknown envelope version {}",
+                header[0]
+            )));
+        }
+        let construction = take_field(&mut rest)?;
+        if let Some(identifier) = self.construction()
+            && !construction.is_empty()
+            && construction != identifier.as_str().as_bytes()
+        {
+            return Err(Error::MalformedCiphertext(
+                "Envelope was sealed with a different construction".into(),
+            ));
+        }
+        let variation = take_field(&mut rest)?.to_vec();
+        let tag = take_field(&mut rest)?;
+
+        let unsealed: Result<_> = (|| {
+            let operation = self.unseal()?;
+            match header[1] {
+                0 => {}
+                1 => operation.set_variation(VariationParam::arbitrary(variation))?,
+                2 => operation.set_variation(VariationParam::random(variation))?,
+                3 => operation.set_variation(VariationParam::unique(variation))?,
+                other => {
+                    return Err(Error::MalformedCiphertext(format!(
+                        "Unknown envelope variation type {}",
+                        other
+                    )));
+                }
+            }
+            operation.update_aad(aad)?;
+            operation.finish_aad()?;
+            let plaintext = operation.update(rest)?;
+            Ok((plaintext, operation.finish(tag)?))
+        })();
+        #[cfg(feature = "metrics")]
+        report_unseal(self.metrics_sink(), &unsealed);
+        let (mut plaintext, mut result) = unsealed?;
+        plaintext.append(&mut result.data);
+        result.data = plaintext;
+        Ok(result)
+    }
+
+    /// Export the key material of the [`BindRune`], wrapped (encrypted) under `wrapping_key`, for
+    /// example to escrow it.
+    ///
+    /// A `BindRune` whose schema has [`crate::runes::EscrowPolicy::Forbidden`] must reject export
+    /// with [`Error::ExportForbidden`]; implementations that override this method are responsible
+    /// for checking [`Schema::escrow_policy`].  The default implementation performs that check
+    /// and otherwise returns [`Error::Unsupported`].
+    fn export_wrapped(&self, _wrapping_key: &dyn BindRune) -> Result<Vec<u8>> {
+        if self.schema().escrow_policy() == EscrowPolicy::Forbidden {
+            return Err(Error::ExportForbidden("Escrow policy forbids export".into()));
+        }
+        Err(Error::Unsupported("Wrapped export is not supported".into()))
+    }
+
+    /// Return attestation evidence for the [`BindRune`]'s key, in the format reported by its
+    /// [`crate::runes::Rune::Attestation`], incorporating `challenge` to prove freshness.  Only
+    /// available if the schema contains `Rune::Attestation`.  Like [`BindRune::public_key`], the
+    /// default fails with [`Error::KeyUsageViolation`] for a `BindRune` whose schema
+    /// [`Schema::is_symmetric_only`], since attestation evidence binds a public key, and with
+    /// [`Error::Unsupported`] otherwise.
+    fn attest(&self, _challenge: &[u8]) -> Result<Vec<u8>> {
+        if self.schema().is_symmetric_only() {
+            return Err(Error::KeyUsageViolation("Symmetric keys cannot be attested".into()));
+        }
+        Err(Error::Unsupported("Attestation is not supported".into()))
+    }
+
+    /// Return the operations recorded in the [`BindRune`]'s audit log, oldest first.  Only
+    /// available if the schema contains [`crate::runes::Rune::AuditLogged`]; otherwise
+    /// [`Error::Unsupported`] is returned.
+    fn audit_log(&self) -> Result<Vec<AuditEntry>> {
+        Err(Error::Unsupported("Audit logging is not supported".into()))
+    }
+
+    /// Return a statement of the number of operations performed with the [`BindRune`], signed by
+    /// the provider.  Only available if the schema contains [`crate::runes::Rune::UsageProof`];
+    /// otherwise [`Error::Unsupported`] is returned, which is the default.
+    fn usage_proof(&self) -> Result<SignedUsageCount> {
+        Err(Error::Unsupported("Usage proofs are not supported".into()))
+    }
+
+    /// Return the current state of the [`BindRune`]'s enforced-limit counters, so the caller can
+    /// persist it and restore it with [`Provider::restore_counters`] after a restart.  The
+    /// default implementation reads the remaining budgets from the `Enforced*` runes of
+    /// [`BindRune::schema`].
+    fn counter_snapshot(&self) -> CounterState {
+        let schema = self.schema();
+        let remaining = |enforced| schema.enforced_limit(&enforced).unwrap_or(u128::MAX);
+        CounterState {
+            message_count_remaining: remaining(Rune::EnforcedMessageLimit(0)),
+            total_data_remaining: remaining(Rune::EnforcedTotalDataLimit(0)),
+        }
+    }
+
+    /// Returns true if no enforced limit reported by [`BindRune::schema`] is higher than in
+    /// `previous`, an earlier schema of the same `BindRune`.  Enforced limits only ever count
+    /// down, so a higher value, or the disappearance of an enforced limit, indicates an
+    /// accounting bug that could allow the key to be used beyond its safe bounds.
+    ///
+    /// Providers should `debug_assert!` this whenever they update their counters.
+    fn verify_counter_monotonic(&self, previous: &Schema) -> bool {
+        let current = self.schema();
+        [
+            Rune::EnforcedMessageLimit(0),
+            Rune::EnforcedMessageSizeLimit(0),
+            Rune::EnforcedTotalDataLimit(0),
+        ]
+        .iter()
+        .all(|enforced| {
+            match (previous.enforced_limit(enforced), current.enforced_limit(enforced)) {
+                (Some(previous), Some(current)) => current <= previous,
+                (Some(_), None) => false,
+                (None, _) => true,
+            }
+        })
+    }
+}
+
+/// The remaining message and data budgets of a [`BindRune`] with enforced limits, as captured by
+/// [`BindRune::counter_snapshot`].  `u128::MAX` indicates that the corresponding limit is not
+/// enforced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterState {
+    pub message_count_remaining: u128,
+    pub total_data_remaining: u128,
 }
 
+/// The kind of operation recorded in an [`AuditEntry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditedOperation {
+    Seal,
+    Unseal,
+}
+
+/// A record of a single use of a [`BindRune`], retained by providers that support
+/// [`crate::runes::Rune::AuditLogged`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// The position of the entry in the log.  Sequence numbers start at zero and have no gaps, so
+    /// a missing entry is detectable.
+    pub sequence: u64,
+    pub operation: AuditedOperation,
+    /// The time at which the provider started the operation.
+    pub time: Zoned,
+}
+
+
+/// A verifiable statement of how many operations have been performed with a [`BindRune`],
+/// returned by [`BindRune::usage_proof`].
+///
+/// The statement is signed with the provider's device identity key, not with the key of the
+/// `BindRune`, so verifiers must obtain the device's public identity key through a trusted
+/// channel, such as a certificate chain or attestation, and the proof is only as trustworthy as
+/// the protection of that key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedUsageCount {
+    /// A provider-assigned identifier of the `BindRune`'s key, so that proofs cannot be replayed
+    /// for another key.
+    pub key_id: Vec<u8>,
+    /// The number of seal and unseal operations performed with the key.
+    pub count: u64,
+    /// The device identity key's signature over [`SignedUsageCount::statement`].
+    pub signature: Vec<u8>,
+}
+
+impl SignedUsageCount {
+    /// Returns the bytes covered by the signature: the ASCII label `sygaldry usage proof v1`,
+    /// the length-prefixed `key_id` and the big-endian 64-bit `count`.
+    pub fn statement(&self) -> Result<Vec<u8>> {
+        let mut statement = b"sygaldry usage proof v1".to_vec();
+        put_field(&mut statement, &self.key_id)?;
+        statement.extend_from_slice(&self.count.to_be_bytes());
+        Ok(statement)
+    }
+}
 
 pub trait Operation {
     fn variation_size(&self) -> VariationSize;
@@ -155,8 +540,26 @@ pub trait Operation {
 
     fn update(&self, data: &[u8]) -> Result<Vec<u8>>;
     fn finish(&self, data: &[u8]) -> Result<OperationResult>;
+
+    /// Bind the operation to a monotonic version counter, such as a firmware version, for
+    /// [`BindRune`]s with [`crate::runes::Rune::AntiRollback`].  When sealing, the provider
+    /// rejects a version lower than the highest version it has previously sealed with
+    /// [`Error::RollbackDetected`], and otherwise raises its high-water mark to `version` when the
+    /// operation finishes.
+    ///
+    /// The default implementation returns [`Error::Unsupported`].
+    fn set_version(&self, _version: u64) -> Result<()> {
+        Err(Error::Unsupported("Version binding is not supported".into()))
+    }
 }
 
+/// The result of a completed [`Operation`].
+///
+/// An `OperationResult` never contains key material.  Its `data` is wiped when the result is
+/// dropped, because after [`BindRune::unseal`] it holds plaintext.  Output parameters are not
+/// wiped, since variations and authentication tags are transmitted alongside the sealed data and
+/// are not secret.  Callers that move `data` out of the result (e.g. with [`core::mem::take`])
+/// take over responsibility for wiping it.
 #[derive(Debug, Clone)]
 pub struct OperationResult {
     pub data: Vec<u8>,
@@ -165,14 +568,107 @@ pub struct OperationResult {
     pub total_data_remaining: u128,
 }
 
+impl Drop for OperationResult {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum OutputParameter {
     AuthenticationTag(Vec<u8>),
     Variation(VariationParam),
 }
 
+impl fmt::Display for OutputParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputParameter::AuthenticationTag(tag) => write!(f, "tag:{}", hex_encode(tag)),
+            OutputParameter::Variation(variation) => write!(f, "variation:{}", variation),
+        }
+    }
+}
+
 pub trait Provider {
-    fn forge(&self, label: &str, desired_properties: Schema) -> Result<Box<dyn BindRune>>;
+    /// Forge a new [`BindRune`] with the specified label, using a construction that satisfies
+    /// `desired_properties`.  If no available construction satisfies them, the request is rejected
+    /// with [`crate::error::Error::UnsatisfiableRequirements`], carrying the unmet requirements as
+    /// described by [`crate::construction::ConstructionRegistry::select`].
+    ///
+    /// Providers select among satisfying constructions as described by
+    /// [`crate::construction::ConstructionRegistry::best_match`].  In particular, a schema that
+    /// specifies no security requirements, such as the default schema produced by
+    /// `SchemaBuilder::new().build()`, is not an error.  It forges a key with the weakest
+    /// construction that supports the default message, message size and total data limits and
+    /// generates variations automatically, so callers who care about security properties must
+    /// state them.
+    ///
+    /// The default implementation selects the construction from
+    /// [`Provider::construction_registry`] with
+    /// [`crate::construction::ConstructionRegistry::select`], and forges the key with
+    /// [`Provider::forge_construction`].  With the `metrics` feature, it reports the construction
+    /// of each successful forge to the registry's
+    /// [`crate::construction::ConstructionRegistry::metrics_sink`].  Providers without a registry
+    /// must override it.
+    fn forge(&self, label: &str, desired_properties: Schema) -> Result<Box<dyn BindRune>> {
+        let Some(registry) = self.construction_registry() else {
+            return Err(Error::Unsupported("Provider has no construction registry".into()));
+        };
+        let construction = registry.select(&desired_properties)?;
+        let bind_rune = self.forge_construction(label, construction, desired_properties)?;
+        #[cfg(feature = "metrics")]
+        registry.metrics_sink().construction_selected(&construction.identifier());
+        Ok(bind_rune)
+    }
+
+    /// Returns the constructions the provider offers, used by the default implementation of
+    /// [`Provider::forge`].  The default implementation returns `None`.
+    fn construction_registry(&self) -> Option<&ConstructionRegistry> {
+        None
+    }
+
+    /// Returns the schema of every construction the provider can forge, e.g. to present the
+    /// available options before forging.  These are reporting schemas, as returned by
+    /// [`crate::construction::Construction::schema`], so limits appear as `Enforced*` runes where
+    /// the construction enforces them.
+    ///
+    /// The default implementation lists the constructions in
+    /// [`Provider::construction_registry`], in identifier order, or none if there is no registry.
+    fn capabilities(&self) -> Vec<Schema> {
+        self.construction_registry()
+            .map(|registry| {
+                registry.iter().map(|(_, construction)| construction.schema()).collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns true if [`Provider::forge`] would find a construction satisfying `requirements`,
+    /// without generating a key, so that callers can cheaply fall back to another provider
+    /// rather than handle [`Error::UnsatisfiableRequirements`].  Forging may still fail for other
+    /// reasons, such as a communication error.
+    ///
+    /// The default implementation looks in [`Provider::construction_registry`] with
+    /// [`crate::construction::ConstructionRegistry::find_best`], and returns false if there is no
+    /// registry.
+    fn can_forge(&self, requirements: &Schema) -> bool {
+        self.construction_registry()
+            .is_some_and(|registry| registry.find_best(requirements).is_some())
+    }
+
+    /// Forge a new [`BindRune`] with the specified label using `construction`, which has already
+    /// been selected as satisfying `desired_properties`.  Called by the default implementation
+    /// of [`Provider::forge`].  The default implementation returns [`Error::Unsupported`].
+    fn forge_construction(
+        &self,
+        _label: &str,
+        construction: &dyn Construction,
+        _desired_properties: Schema,
+    ) -> Result<Box<dyn BindRune>> {
+        Err(Error::Unsupported(format!(
+            "Forging with construction {} is not supported",
+            construction.identifier().as_str()
+        )))
+    }
     fn forge_shareable(
         &self,
         label: &str,
@@ -180,4 +676,942 @@ pub trait Provider {
         desired_properties: Schema,
     ) -> Result<Box<dyn BindRune>>;
     fn retrieve(&self, label: &str) -> Result<Box<dyn BindRune>>;
+
+    /// Import externally-generated key material under the specified label, for use with the
+    /// specified construction and parameters.  This supports migrating keys from other systems.
+    ///
+    /// Implementations must validate the parameters with
+    /// [`crate::construction::Construction::check_params`] and the key material with
+    /// [`crate::construction::Construction::check_raw_key`], reject the import with
+    /// [`Error::UnsatisfiableRequirements`] if the construction does not satisfy `requirements`,
+    /// and report [`crate::runes::KeyOrigin::Imported`] from the resulting
+    /// [`BindRune::schema`].  Providers that never accept raw key material, for example because
+    /// their policy requires keys to be generated on-device, return [`Error::Unsupported`], which
+    /// is the default.
+    fn import_raw(
+        &self,
+        _label: &str,
+        _key_material: &[u8],
+        _construction: &ConstructionParams,
+        _requirements: &Schema,
+    ) -> Result<Box<dyn BindRune>> {
+        Err(Error::Unsupported("Raw key import is not supported".into()))
+    }
+
+    /// Forge a new [`BindRune`] with the specified label and seal `plaintext` with it, with no
+    /// associated data, in a single interaction with the backend.  This saves a round trip for
+    /// ephemeral keys that seal exactly one message, as in hybrid encryption.
+    ///
+    /// The `BindRune` is returned along with the sealed result so that the caller can persist it,
+    /// or simply drop it if the key is not needed again.  Forging fails as described in
+    /// [`Provider::forge`].  The default implementation forges and seals in two steps, reporting
+    /// the outcome of sealing to the [`BindRune::metrics_sink`] with the `metrics` feature.
+    fn forge_and_seal(
+        &self,
+        label: &str,
+        requirements: &Schema,
+        plaintext: &[u8],
+    ) -> Result<(Box<dyn BindRune>, OperationResult)> {
+        let bind_rune = self.forge(label, requirements.clone())?;
+        let sealed = bind_rune.seal().and_then(|operation| {
+            operation.finish_aad()?;
+            operation.finish(plaintext)
+        });
+        #[cfg(feature = "metrics")]
+        report_seal(bind_rune.metrics_sink(), &sealed);
+        Ok((bind_rune, sealed?))
+    }
+
+    /// Retrieve the [`BindRune`] with the specified label, with its enforced-limit counters
+    /// restored from `state`, a snapshot previously taken with [`BindRune::counter_snapshot`].
+    ///
+    /// Providers that keep counters only in memory lose them on restart.  Rather than risk using
+    /// a key past its safe bound, such providers must conservatively treat the budgets of a
+    /// `BindRune` whose counters were not restored as fully consumed, rendering it unusable.
+    /// Restoring can only lower the remaining budgets, never raise them, so replaying an old
+    /// snapshot cannot extend the life of a key.
+    ///
+    /// The default implementation returns [`Error::Unsupported`].
+    fn restore_counters(&self, _label: &str, _state: CounterState) -> Result<Box<dyn BindRune>> {
+        Err(Error::Unsupported("Counter restoration is not supported".into()))
+    }
+}
+
+/// Forges a [`BindRune`] with `provider`, with requirements built by `configure` from a fresh
+/// [`SchemaBuilder`], e.g. `|builder| builder.security_bits(128).quantum_resistance(true)`.
+/// This is equivalent to building the schema with [`SchemaBuilder::try_build`] and calling
+/// [`Provider::forge`] with it, so contradictory requirements fail with
+/// [`Error::ConflictingRequirements`] without forging.
+pub fn forge_with<P: Provider + ?Sized>(
+    provider: &P,
+    label: &str,
+    configure: impl FnOnce(SchemaBuilder) -> SchemaBuilder,
+) -> Result<Box<dyn BindRune>> {
+    provider.forge(label, configure(SchemaBuilder::new()).try_build()?)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::construction::ParamValue;
+    use crate::construction::tests::{TestConstruction, registry, schema_with_bits};
+    use crate::ct;
+    use crate::runes::{KeyOrigin, OriginIdentity};
+    use crate::testing::{MockProvider, ProviderCall};
+    use alloc::{rc::Rc, string::ToString};
+    use core::cell::{Cell, RefCell};
+
+    /// A [`BindRune`] that reports the schema and identifier of the construction it was forged
+    /// with, and supports no operations.
+    pub(crate) struct ConstructionBindRune {
+        pub(crate) schema: Schema,
+        pub(crate) construction: ConstructionIdentifier,
+    }
+
+    impl BindRune for ConstructionBindRune {
+        fn seal(&self) -> Result<Box<dyn Operation>> {
+            Err(Error::Unsupported("Test BindRunes cannot seal".into()))
+        }
+
+        fn unseal(&self) -> Result<Box<dyn Operation>> {
+            Err(Error::Unsupported("Test BindRunes cannot unseal".into()))
+        }
+
+        fn schema(&self) -> Schema {
+            self.schema.clone()
+        }
+
+        fn construction(&self) -> Option<ConstructionIdentifier> {
+            Some(self.construction.clone())
+        }
+    }
+
+    /// A provider that relies on the default [`Provider::forge`], forging
+    /// [`ConstructionBindRune`]s.
+    pub(crate) struct RegistryProvider {
+        pub(crate) registry: ConstructionRegistry,
+    }
+
+    impl Provider for RegistryProvider {
+        fn construction_registry(&self) -> Option<&ConstructionRegistry> {
+            Some(&self.registry)
+        }
+
+        fn forge_construction(
+            &self,
+            _label: &str,
+            construction: &dyn Construction,
+            _desired_properties: Schema,
+        ) -> Result<Box<dyn BindRune>> {
+            Ok(Box::new(ConstructionBindRune {
+                schema: construction.schema(),
+                construction: construction.identifier(),
+            }))
+        }
+
+        fn forge_shareable(
+            &self,
+            _label: &str,
+            _uuid: u128,
+            _desired_properties: Schema,
+        ) -> Result<Box<dyn BindRune>> {
+            Err(Error::Unsupported("Shareable keys are not supported".into()))
+        }
+
+        fn retrieve(&self, _label: &str) -> Result<Box<dyn BindRune>> {
+            Err(Error::UnknownLabel)
+        }
+
+        fn import_raw(
+            &self,
+            _label: &str,
+            key_material: &[u8],
+            construction: &ConstructionParams,
+            requirements: &Schema,
+        ) -> Result<Box<dyn BindRune>> {
+            let Some(selected) = self.registry.get(construction.construction()) else {
+                return Err(Error::InvalidParameter("Unknown construction".into()));
+            };
+            selected.check_params(construction)?;
+            selected.check_raw_key(key_material)?;
+            let schema = selected.schema();
+            if !schema.satisfies(requirements) {
+                return Err(Error::UnsatisfiableRequirements(schema.delta(requirements)));
+            }
+            Ok(Box::new(ConstructionBindRune {
+                schema: schema
+                    .merge(&SchemaBuilder::new().key_origin(KeyOrigin::Imported).build())?,
+                construction: selected.identifier(),
+            }))
+        }
+    }
+
+    /// An [`Operation`] that passes data through unchanged.
+    pub(crate) struct PassthroughOperation;
+
+    impl Operation for PassthroughOperation {
+        fn variation_size(&self) -> VariationSize {
+            None
+        }
+
+        fn set_variation(&self, _variation: VariationParam) -> Result<()> {
+            Ok(())
+        }
+
+        fn update_aad(&self, _aad: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        fn finish_aad(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn update(&self, data: &[u8]) -> Result<Vec<u8>> {
+            Ok(data.to_vec())
+        }
+
+        fn finish(&self, data: &[u8]) -> Result<OperationResult> {
+            Ok(OperationResult {
+                data: data.to_vec(),
+                output_parameters: Vec::new(),
+                message_count_remaining: u128::MAX,
+                total_data_remaining: u128::MAX,
+            })
+        }
+    }
+
+    /// An [`Operation`] of a toy authenticated cipher for exercising the [`BindRune`] defaults.
+    /// Data passes through unchanged, and is authenticated together with the associated data and
+    /// the [`BindRune`]'s domain by a non-cryptographic FNV-1a tag.
+    pub(crate) struct ToyAeadOperation {
+        sealing: bool,
+        authenticated: RefCell<Vec<u8>>,
+    }
+
+    impl ToyAeadOperation {
+        pub(crate) fn new(sealing: bool, schema: &Schema) -> Self {
+            let domain = schema.domain().unwrap_or_default();
+            let authenticated = RefCell::new(Vec::new());
+            put_field(&mut authenticated.borrow_mut(), domain.as_bytes()).unwrap();
+            Self { sealing, authenticated }
+        }
+
+        fn tag(&self) -> Vec<u8> {
+            let tag =
+                self.authenticated.borrow().iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+                    (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+                });
+            tag.to_be_bytes().to_vec()
+        }
+    }
+
+    impl Operation for ToyAeadOperation {
+        fn variation_size(&self) -> VariationSize {
+            None
+        }
+
+        fn set_variation(&self, _variation: VariationParam) -> Result<()> {
+            Ok(())
+        }
+
+        fn update_aad(&self, aad: &[u8]) -> Result<()> {
+            put_field(&mut self.authenticated.borrow_mut(), aad)
+        }
+
+        fn finish_aad(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn update(&self, data: &[u8]) -> Result<Vec<u8>> {
+            self.authenticated.borrow_mut().extend_from_slice(data);
+            Ok(data.to_vec())
+        }
+
+        fn finish(&self, data: &[u8]) -> Result<OperationResult> {
+            let (data, output_parameters) = if self.sealing {
+                let data = self.update(data)?;
+                (data, vec![OutputParameter::AuthenticationTag(self.tag())])
+            } else if ct::ct_eq(data, &self.tag()) {
+                (Vec::new(), Vec::new())
+            } else {
+                return Err(Error::AuthenticationFailed);
+            };
+            Ok(OperationResult {
+                data,
+                output_parameters,
+                message_count_remaining: u128::MAX,
+                total_data_remaining: u128::MAX,
+            })
+        }
+    }
+
+    /// A [`BindRune`] with the specified schema using [`ToyAeadOperation`]s.
+    pub(crate) struct ToyAeadBindRune {
+        pub(crate) schema: Schema,
+    }
+
+    impl BindRune for ToyAeadBindRune {
+        fn seal(&self) -> Result<Box<dyn Operation>> {
+            self.schema.check_usage(KeyUsage::Encrypt)?;
+            Ok(Box::new(ToyAeadOperation::new(true, &self.schema)))
+        }
+
+        fn unseal(&self) -> Result<Box<dyn Operation>> {
+            self.schema.check_usage(KeyUsage::Decrypt)?;
+            Ok(Box::new(ToyAeadOperation::new(false, &self.schema)))
+        }
+
+        fn schema(&self) -> Schema {
+            self.schema.clone()
+        }
+
+        fn overhead(&self) -> Overhead {
+            Overhead { tag_bytes: 8, ..Overhead::default() }
+        }
+    }
+
+    /// The remaining budgets of a [`CountingBindRune`], shared with its operations.
+    struct Counters {
+        messages: Cell<u128>,
+        bytes: Cell<u128>,
+    }
+
+    /// An [`Operation`] that passes data through unchanged, counting down the budgets of its
+    /// [`CountingBindRune`].
+    struct CountingOperation {
+        counters: Rc<Counters>,
+    }
+
+    impl Operation for CountingOperation {
+        fn variation_size(&self) -> VariationSize {
+            None
+        }
+
+        fn set_variation(&self, _variation: VariationParam) -> Result<()> {
+            Ok(())
+        }
+
+        fn update_aad(&self, _aad: &[u8]) -> Result<()> {
+            Ok(())
+        }
+
+        fn finish_aad(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn update(&self, data: &[u8]) -> Result<Vec<u8>> {
+            let bytes = self.counters.bytes.get().checked_sub(data.len() as u128);
+            let Some(bytes) = bytes else {
+                return Err(Error::TotalDataTooLong("Data budget exhausted".into()));
+            };
+            self.counters.bytes.set(bytes);
+            Ok(data.to_vec())
+        }
+
+        fn finish(&self, data: &[u8]) -> Result<OperationResult> {
+            let data = self.update(data)?;
+            let counters = &self.counters;
+            counters.messages.set(counters.messages.get().saturating_sub(1));
+            Ok(OperationResult {
+                data,
+                output_parameters: Vec::new(),
+                message_count_remaining: counters.messages.get(),
+                total_data_remaining: counters.bytes.get(),
+            })
+        }
+    }
+
+    /// A software [`BindRune`] that enforces message and data limits with in-memory counters.
+    struct CountingBindRune {
+        counters: Rc<Counters>,
+    }
+
+    impl BindRune for CountingBindRune {
+        fn seal(&self) -> Result<Box<dyn Operation>> {
+            Ok(Box::new(CountingOperation { counters: self.counters.clone() }))
+        }
+
+        fn unseal(&self) -> Result<Box<dyn Operation>> {
+            Err(Error::Unsupported("Counting BindRunes cannot unseal".into()))
+        }
+
+        fn schema(&self) -> Schema {
+            SchemaBuilder::new()
+                .security_bits(128)
+                .enforced_message_limit(self.counters.messages.get())
+                .and_then(|builder| builder.enforced_total_data_limit(self.counters.bytes.get()))
+                .unwrap()
+                .build()
+        }
+    }
+
+    /// A provider of [`CountingBindRune`]s whose counters are lost on restart, so they must be
+    /// restored with [`Provider::restore_counters`].
+    struct CountingProvider {
+        limits: CounterState,
+    }
+
+    impl CountingProvider {
+        fn bind_rune(&self, state: CounterState) -> Box<dyn BindRune> {
+            let counters = Counters {
+                messages: Cell::new(
+                    state.message_count_remaining.min(self.limits.message_count_remaining),
+                ),
+                bytes: Cell::new(state.total_data_remaining.min(self.limits.total_data_remaining)),
+            };
+            Box::new(CountingBindRune { counters: Rc::new(counters) })
+        }
+    }
+
+    impl Provider for CountingProvider {
+        fn forge(&self, _label: &str, _desired_properties: Schema) -> Result<Box<dyn BindRune>> {
+            Ok(self.bind_rune(self.limits))
+        }
+
+        fn forge_shareable(
+            &self,
+            _label: &str,
+            _uuid: u128,
+            _desired_properties: Schema,
+        ) -> Result<Box<dyn BindRune>> {
+            Err(Error::Unsupported("Shareable keys are not supported".into()))
+        }
+
+        fn retrieve(&self, _label: &str) -> Result<Box<dyn BindRune>> {
+            Ok(self.bind_rune(CounterState { message_count_remaining: 0, total_data_remaining: 0 }))
+        }
+
+        fn restore_counters(&self, _label: &str, state: CounterState) -> Result<Box<dyn BindRune>> {
+            Ok(self.bind_rune(state))
+        }
+    }
+
+    /// A software [`BindRune`] satisfying [`Rune::AuditLogged`] with an in-memory log.
+    struct AuditingBindRune {
+        log: RefCell<Vec<AuditEntry>>,
+    }
+
+    impl AuditingBindRune {
+        fn record(&self, operation: AuditedOperation) {
+            let mut log = self.log.borrow_mut();
+            let sequence = log.len() as u64;
+            let time = jiff::civil::date(2026, 1, 1).at(0, 0, sequence as i8, 0);
+            log.push(AuditEntry {
+                sequence,
+                operation,
+                time: time.to_zoned(jiff::tz::TimeZone::UTC).unwrap(),
+            });
+        }
+    }
+
+    impl BindRune for AuditingBindRune {
+        fn seal(&self) -> Result<Box<dyn Operation>> {
+            self.record(AuditedOperation::Seal);
+            Ok(Box::new(PassthroughOperation))
+        }
+
+        fn unseal(&self) -> Result<Box<dyn Operation>> {
+            self.record(AuditedOperation::Unseal);
+            Ok(Box::new(PassthroughOperation))
+        }
+
+        fn schema(&self) -> Schema {
+            SchemaBuilder::new().security_bits(128).audit_logged().build()
+        }
+
+        fn audit_log(&self) -> Result<Vec<AuditEntry>> {
+            Ok(self.log.borrow().clone())
+        }
+    }
+
+    #[test]
+    fn audit_log_records_each_seal() {
+        let bind_rune = AuditingBindRune { log: RefCell::new(Vec::new()) };
+
+        bind_rune.seal_envelope(b"first", b"").unwrap();
+        bind_rune.seal_envelope(b"second", b"").unwrap();
+
+        let log = bind_rune.audit_log().unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.iter().map(|entry| entry.sequence).collect::<Vec<_>>(), [0, 1]);
+        assert!(log.iter().all(|entry| entry.operation == AuditedOperation::Seal));
+        assert!(log[0].time < log[1].time);
+    }
+
+    #[test]
+    fn audit_log_is_unsupported_by_default() {
+        let bind_rune = ConstructionBindRune {
+            schema: schema_with_bits(128),
+            construction: ConstructionIdentifier::new("aes-128"),
+        };
+
+        assert!(matches!(bind_rune.audit_log(), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn forge_with_default_schema_uses_weakest_construction() {
+        let provider = RegistryProvider {
+            registry: registry([
+                TestConstruction::new("aes-256", schema_with_bits(255)),
+                TestConstruction::new("aes-128", schema_with_bits(128)),
+            ]),
+        };
+
+        for _ in 0..3 {
+            let bind_rune = provider.forge("key", SchemaBuilder::new().build()).unwrap();
+            assert_eq!(bind_rune.construction().unwrap().as_str(), "aes-128");
+        }
+    }
+
+    #[test]
+    fn forge_without_registry_is_unsupported() {
+        struct NoRegistry;
+        impl Provider for NoRegistry {
+            fn forge_shareable(&self, _: &str, _: u128, _: Schema) -> Result<Box<dyn BindRune>> {
+                Err(Error::UnknownLabel)
+            }
+            fn retrieve(&self, _: &str) -> Result<Box<dyn BindRune>> {
+                Err(Error::UnknownLabel)
+            }
+        }
+
+        let result = NoRegistry.forge("key", SchemaBuilder::new().build());
+
+        assert!(matches!(result, Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn forge_selects_a_satisfying_construction_from_the_registry() {
+        let provider = RegistryProvider {
+            registry: registry([
+                TestConstruction::new("aes-256", schema_with_bits(255)),
+                TestConstruction::new("aes-128", schema_with_bits(128)),
+            ]),
+        };
+
+        let bind_rune = provider.forge("key", schema_with_bits(192)).unwrap();
+        assert_eq!(bind_rune.construction().unwrap().as_str(), "aes-256");
+        assert_eq!(bind_rune.schema(), schema_with_bits(255));
+
+        let result = provider.forge("key", SchemaBuilder::new().quantum_resistance(true).build());
+        assert!(matches!(result, Err(Error::UnsatisfiableRequirements(_))));
+    }
+
+    #[test]
+    fn domains_do_not_cross_decrypt() {
+        let bind_rune = |domain| ToyAeadBindRune {
+            schema: SchemaBuilder::new().security_bits(128).domain_separation(domain).build(),
+        };
+        let (email, files) = (bind_rune("email"), bind_rune("files"));
+
+        let envelope = email.seal_envelope(b"message", b"").unwrap();
+
+        assert_eq!(email.unseal_envelope(&envelope, b"").unwrap().data, b"message");
+        assert!(matches!(files.unseal_envelope(&envelope, b""), Err(Error::AuthenticationFailed)));
+        assert!(!files.schema().satisfies(&email.schema()));
+    }
+
+    #[test]
+    fn import_raw_checks_key_size() {
+        let provider = RegistryProvider {
+            registry: registry([
+                TestConstruction {
+                    raw_key_size: Some(16),
+                    ..TestConstruction::new("aes-128", schema_with_bits(128))
+                },
+                TestConstruction::new("generated-only", schema_with_bits(128)),
+            ]),
+        };
+        let params = |identifier| ConstructionParams::new(ConstructionIdentifier::new(identifier));
+        let requirements = schema_with_bits(128);
+
+        let imported = provider.import_raw("key", &[7; 16], &params("aes-128"), &requirements);
+        let imported = imported.unwrap().schema();
+        assert!(imported.satisfies(&SchemaBuilder::new().key_origin(KeyOrigin::Imported).build()));
+        assert!(imported.satisfies(&requirements));
+
+        assert!(matches!(
+            provider.import_raw("key", &[7; 32], &params("aes-128"), &requirements),
+            Err(Error::InvalidKeyMaterial(_))
+        ));
+        assert!(matches!(
+            provider.import_raw("key", &[7; 16], &params("generated-only"), &requirements),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(matches!(
+            provider.import_raw("key", &[7; 16], &params("aes-128"), &schema_with_bits(192)),
+            Err(Error::UnsatisfiableRequirements(_))
+        ));
+    }
+
+    #[test]
+    fn escrow_forbidden_rejects_export_wrapped() {
+        let signing = |policy| ToyAeadBindRune {
+            schema: SchemaBuilder::new()
+                .key_usage(KeyUsage::Sign)
+                .security_bits(128)
+                .escrow(policy)
+                .build(),
+        };
+        let wrapping_key = ToyAeadBindRune { schema: schema_with_bits(128) };
+
+        assert!(matches!(
+            signing(EscrowPolicy::Forbidden).export_wrapped(&wrapping_key),
+            Err(Error::ExportForbidden(_))
+        ));
+        assert!(matches!(
+            signing(EscrowPolicy::Required).export_wrapped(&wrapping_key),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn bind_rune_overhead_defaults_to_none() {
+        let bind_rune = ConstructionBindRune {
+            schema: schema_with_bits(128),
+            construction: ConstructionIdentifier::new("aes-128-ctr"),
+        };
+
+        assert_eq!(bind_rune.overhead().total(), 0);
+        assert_eq!(ToyAeadBindRune { schema: schema_with_bits(128) }.overhead().total(), 8);
+    }
+
+    #[test]
+    fn dropping_bind_rune_wipes_cached_schedule() {
+        /// A [`BindRune`] that caches an expanded key schedule in a caller-supplied buffer, so
+        /// the test can inspect the buffer after the `BindRune` is dropped.
+        struct CachingBindRune<'a> {
+            schedule: &'a mut [u8],
+        }
+
+        impl Drop for CachingBindRune<'_> {
+            fn drop(&mut self) {
+                self.schedule.zeroize();
+            }
+        }
+
+        impl BindRune for CachingBindRune<'_> {
+            fn seal(&self) -> Result<Box<dyn Operation>> {
+                Ok(Box::new(PassthroughOperation))
+            }
+
+            fn unseal(&self) -> Result<Box<dyn Operation>> {
+                Ok(Box::new(PassthroughOperation))
+            }
+
+            fn schema(&self) -> Schema {
+                schema_with_bits(128)
+            }
+        }
+
+        let mut schedule = [0x5a; 176];
+        let bind_rune = CachingBindRune { schedule: &mut schedule };
+        bind_rune.seal_envelope(b"data", b"").unwrap();
+
+        drop(bind_rune);
+
+        assert!(schedule.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn restored_counters_continue_the_countdown() {
+        let limits = CounterState { message_count_remaining: 10, total_data_remaining: 100 };
+        let provider = CountingProvider { limits };
+        let bind_rune = provider.forge("key", schema_with_bits(128)).unwrap();
+        bind_rune.seal_envelope(&[0; 10], b"").unwrap();
+        bind_rune.seal_envelope(&[0; 10], b"").unwrap();
+        let snapshot = bind_rune.counter_snapshot();
+        assert_eq!(snapshot, CounterState { message_count_remaining: 8, total_data_remaining: 80 });
+        drop(bind_rune);
+
+        let restored = provider.restore_counters("key", snapshot).unwrap();
+        assert_eq!(restored.counter_snapshot(), snapshot);
+        let previous = restored.schema();
+        restored.seal_envelope(&[0; 10], b"").unwrap();
+        assert_eq!(
+            restored.counter_snapshot(),
+            CounterState { message_count_remaining: 7, total_data_remaining: 70 }
+        );
+        assert!(restored.verify_counter_monotonic(&previous));
+
+        let unrestored = provider.retrieve("key").unwrap();
+        assert!(matches!(unrestored.seal_envelope(&[0; 10], b""), Err(Error::TotalDataTooLong(_))));
+
+        let replayed = CounterState { message_count_remaining: 20, total_data_remaining: 200 };
+        assert_eq!(provider.restore_counters("key", replayed).unwrap().counter_snapshot(), limits);
+    }
+
+    #[test]
+    fn increased_enforced_counter_is_detected() {
+        let reporting = |messages| ConstructionBindRune {
+            schema: SchemaBuilder::new()
+                .security_bits(128)
+                .enforced_message_limit(messages)
+                .unwrap()
+                .build(),
+            construction: ConstructionIdentifier::new("aes-128"),
+        };
+        let previous = reporting(10).schema();
+
+        assert!(reporting(10).verify_counter_monotonic(&previous));
+        assert!(reporting(9).verify_counter_monotonic(&previous));
+        assert!(!reporting(11).verify_counter_monotonic(&previous));
+
+        let dropped = ConstructionBindRune {
+            schema: schema_with_bits(128),
+            construction: ConstructionIdentifier::new("aes-128"),
+        };
+        assert!(!dropped.verify_counter_monotonic(&previous));
+    }
+
+    #[test]
+    fn lower_version_is_rejected_after_sealing() {
+        /// A sealing [`Operation`] that checks versions against a shared high-water mark.
+        struct VersionedOperation {
+            high_water: Rc<Cell<u64>>,
+            version: Cell<Option<u64>>,
+        }
+
+        impl Operation for VersionedOperation {
+            fn variation_size(&self) -> VariationSize {
+                None
+            }
+
+            fn set_variation(&self, _variation: VariationParam) -> Result<()> {
+                Ok(())
+            }
+
+            fn update_aad(&self, _aad: &[u8]) -> Result<()> {
+                Ok(())
+            }
+
+            fn finish_aad(&self) -> Result<()> {
+                Ok(())
+            }
+
+            fn update(&self, data: &[u8]) -> Result<Vec<u8>> {
+                Ok(data.to_vec())
+            }
+
+            fn finish(&self, data: &[u8]) -> Result<OperationResult> {
+                if let Some(version) = self.version.get() {
+                    self.high_water.set(version);
+                }
+                PassthroughOperation.finish(data)
+            }
+
+            fn set_version(&self, version: u64) -> Result<()> {
+                if version < self.high_water.get() {
+                    return Err(Error::RollbackDetected(format!(
+                        "Version {} is below {}",
+                        version,
+                        self.high_water.get()
+                    )));
+                }
+                self.version.set(Some(version));
+                Ok(())
+            }
+        }
+
+        let high_water = Rc::new(Cell::new(0));
+        let seal = |version| {
+            let operation =
+                VersionedOperation { high_water: high_water.clone(), version: Cell::new(None) };
+            operation.set_version(version)?;
+            operation.finish(b"firmware")
+        };
+
+        assert!(seal(5).is_ok());
+        assert!(matches!(seal(4), Err(Error::RollbackDetected(_))));
+        assert!(seal(5).is_ok());
+        assert_eq!(high_water.get(), 5);
+        assert!(matches!(PassthroughOperation.set_version(1), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn import_raw_validates_construction_params() {
+        let provider = RegistryProvider {
+            registry: registry([TestConstruction {
+                raw_key_size: Some(32),
+                accepted_params: [(
+                    "key_bits",
+                    vec![ParamValue::Unsigned(128), ParamValue::Unsigned(256)],
+                )]
+                .into(),
+                ..TestConstruction::new("aes", schema_with_bits(128))
+            }]),
+        };
+        let params = |key_bits| {
+            ConstructionParams::new(ConstructionIdentifier::new("aes"))
+                .with("key_bits", ParamValue::Unsigned(key_bits))
+        };
+        let requirements = schema_with_bits(128);
+
+        let imported = provider.import_raw("key", &[7; 32], &params(256), &requirements).unwrap();
+
+        assert_eq!(imported.construction().unwrap().as_str(), "aes");
+        assert!(matches!(
+            provider.import_raw("key", &[7; 32], &params(200), &requirements),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn envelope_round_trips() {
+        let bind_rune = ToyAeadBindRune { schema: schema_with_bits(128) };
+
+        let envelope = bind_rune.seal_envelope(b"message", b"aad").unwrap();
+
+        assert_eq!(envelope[0], ENVELOPE_VERSION);
+        assert_eq!(bind_rune.unseal_envelope(&envelope, b"aad").unwrap().data, b"message");
+        assert!(matches!(
+            bind_rune.unseal_envelope(&envelope, b"other"),
+            Err(Error::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn truncated_or_unknown_envelopes_are_rejected() {
+        let bind_rune = ToyAeadBindRune { schema: schema_with_bits(128) };
+        let envelope = bind_rune.seal_envelope(b"message", b"").unwrap();
+
+        for length in [0, 1, 2, 5, 10] {
+            assert!(matches!(
+                bind_rune.unseal_envelope(&envelope[..length], b""),
+                Err(Error::MalformedCiphertext(_))
+            ));
+        }
+        let mut future = envelope.clone();
+        future[0] = ENVELOPE_VERSION + 1;
+        assert!(matches!(
+            bind_rune.unseal_envelope(&future, b""),
+            Err(Error::MalformedCiphertext(_))
+        ));
+    }
+
+    #[test]
+    fn symmetric_bind_runes_have_no_public_key_or_attestation() {
+        let symmetric = ToyAeadBindRune { schema: schema_with_bits(128) };
+        let key_pair = ConstructionBindRune {
+            schema: SchemaBuilder::new().security_bits(128).public_private_key_pair().build(),
+            construction: ConstructionIdentifier::new("ed25519"),
+        };
+
+        assert!(matches!(symmetric.public_key(), Err(Error::KeyUsageViolation(_))));
+        assert!(matches!(symmetric.attest(b"challenge"), Err(Error::KeyUsageViolation(_))));
+        assert!(matches!(key_pair.public_key(), Err(Error::Unsupported(_))));
+        assert!(matches!(key_pair.attest(b"challenge"), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn variations_round_trip_through_text() {
+        let variation = VariationParam::unique(vec![0x00, 0x01, 0xab]);
+
+        let text = variation.to_string();
+        let parsed: VariationParam = "random:00FF".parse().unwrap();
+
+        assert_eq!(text, "unique:0001ab");
+        let reparsed: VariationParam = text.parse().unwrap();
+        assert_eq!(reparsed.data(), variation.data());
+        assert_eq!(reparsed.variation_type(), &VariationType::Unique);
+        assert_eq!(parsed.data(), [0x00, 0xff]);
+        assert_eq!(parsed.variation_type(), &VariationType::Random);
+        assert_eq!("arbitrary:0A0b".parse::<VariationParam>().unwrap().data(), [0x0a, 0x0b]);
+        for invalid in ["unique", "unique:0", "unique:zz", "nonce:00"] {
+            assert!(matches!(invalid.parse::<VariationParam>(), Err(Error::VariationInvalid(_))));
+        }
+        assert_eq!(OutputParameter::Variation(variation).to_string(), "variation:unique:0001ab");
+        assert_eq!(OutputParameter::AuthenticationTag(vec![0xde, 0xad]).to_string(), "tag:dead");
+    }
+
+    #[test]
+    fn default_subkey_derivation_checks_usage_and_depth() {
+        let bind_rune =
+            |usage| ToyAeadBindRune { schema: SchemaBuilder::new().key_usage(usage).build() };
+        let requirements = SchemaBuilder::new().max_derivation_depth(0).build();
+
+        assert!(matches!(
+            bind_rune(KeyUsage::Encrypt).derive_subkey("subkey", &SchemaBuilder::new().build()),
+            Err(Error::KeyUsageViolation(_))
+        ));
+        assert!(matches!(
+            bind_rune(KeyUsage::Derive).derive_subkey("subkey", &requirements),
+            Err(Error::DerivationTooDeep(_))
+        ));
+        assert!(matches!(
+            bind_rune(KeyUsage::Derive).derive_subkey("subkey", &SchemaBuilder::new().build()),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn verify_meets_reports_the_unmet_runes() {
+        let bind_rune = ToyAeadBindRune { schema: schema_with_bits(128) };
+        let request = SchemaBuilder::new().security_bits(192).quantum_resistance(true).build();
+
+        assert!(bind_rune.verify_meets(&schema_with_bits(112)).is_ok());
+        match bind_rune.verify_meets(&request) {
+            Err(Error::UnsatisfiableRequirements(delta)) => {
+                assert_eq!(delta.runes(), [Rune::SecurityBits(192), Rune::QuantumResistance]);
+            }
+            other => panic!("expected UnsatisfiableRequirements, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn usage_statements_bind_the_key_and_count() {
+        let proof =
+            SignedUsageCount { key_id: vec![0xaa, 0xbb], count: 258, signature: Vec::new() };
+
+        let statement = proof.statement().unwrap();
+
+        let mut expected = b"sygaldry usage proof v1".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 2, 0xaa, 0xbb, 0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(statement, expected);
+        let other_key = SignedUsageCount { key_id: vec![0xaa], ..proof.clone() };
+        assert_ne!(other_key.statement().unwrap(), statement);
+        assert!(matches!(
+            ToyAeadBindRune { schema: schema_with_bits(128) }.usage_proof(),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn forge_with_builds_and_checks_the_requirements() {
+        let provider = MockProvider::new();
+
+        let bind_rune = forge_with(&provider, "key", |builder| {
+            builder.security_bits(128).quantum_resistance(true)
+        })
+        .unwrap();
+        let conflicting = forge_with(&provider, "weak", |builder| {
+            builder.security_bits(100).authentication(OriginIdentity, 2040)
+        });
+
+        let expected = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+        assert_eq!(bind_rune.schema(), expected);
+        assert!(matches!(conflicting, Err(Error::ConflictingRequirements(_))));
+        assert_eq!(
+            provider.calls(),
+            [ProviderCall::Forge { label: "key".into(), desired_properties: expected }]
+        );
+    }
+
+    #[test]
+    fn forge_and_seal_uses_the_forged_bind_rune() {
+        let provider = CountingProvider {
+            limits: CounterState { message_count_remaining: 3, total_data_remaining: 100 },
+        };
+
+        let (bind_rune, result) =
+            provider.forge_and_seal("ephemeral", &schema_with_bits(128), b"data").unwrap();
+
+        assert_eq!(result.data, b"data");
+        assert_eq!(result.message_count_remaining, 2);
+        assert_eq!(result.total_data_remaining, 96);
+        assert_eq!(
+            bind_rune.counter_snapshot(),
+            CounterState { message_count_remaining: 2, total_data_remaining: 96 }
+        );
+    }
 }
@@ -1,12 +1,28 @@
 //! Runes are used to specify the security of a [`crate::provider::BindRune`], along a variety of
 //! axes.
+//!
+//! With the `serde` feature, [`Rune`]s, [`Schema`]s and the results of comparing them, such as
+//! [`CoverageReport`], [`SchemaDiff`] and [`Schema::delta`], can be serialized, for example to
+//! record policy changes in an audit trail.  The serialized form is a stable format: variants and
+//! fields are identified by name, and are only ever added, never renamed or removed, so records
+//! written by older versions of the crate remain readable.
 
-use alloc::{collections::btree_map::BTreeMap, vec::Vec};
+use alloc::{
+    boxed::Box,
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use core::{cmp::Ordering, fmt, mem};
 
-use jiff::{Span, Zoned, civil::DateTime};
+use jiff::{SignedDuration, Span, SpanRelativeTo, Zoned, civil::DateTime};
 
 use crate::{
+    construction::ConstructionIdentifier,
+    encoding::hex_encode,
     error::{Error, Result},
+    kdf::CostParameters,
     platform::PlatformAbstractions, provider::VariationType,
 };
 
@@ -30,6 +46,8 @@ use crate::{
 /// [`Rune::EnforcedTotalDataLimit`] and [`Rune::EnforcedMessageSizeLimit`] to report the values
 /// that will be enforced.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Rune {
     /// If provided, this [`Rune`] indicates that the [`crate::provider::BindRune`] uses a
     /// public/private key pair, allowing the public key to be distributed to other parties for
@@ -132,7 +150,10 @@ pub enum Rune {
     ///
     /// For all classical asymmetric algorithms, the end time is no later than Dec 31, 2035, per the
     /// US government's National Security Memorandum 10.
-    Confidentiality { end_time: DateTime },
+    Confidentiality {
+        #[cfg_attr(feature = "defmt", defmt(Display2Format))]
+        end_time: DateTime,
+    },
 
     /// If provided, this property indicates that the operation ensures the integrity of the data
     /// until the specified year. Years are estimates, and based on NIST SP 800-57 Part 1, table 2.
@@ -157,10 +178,26 @@ pub enum Rune {
     /// epoch.
     ///
     /// Outside of the specified period the operation will be rejected.
-    CryptoPeriod { begin: Zoned, end: Zoned },
+    CryptoPeriod {
+        #[cfg_attr(feature = "defmt", defmt(Display2Format))]
+        #[cfg_attr(feature = "serde", serde(with = "zoned_serde"))]
+        begin: Zoned,
+        #[cfg_attr(feature = "defmt", defmt(Display2Format))]
+        #[cfg_attr(feature = "serde", serde(with = "zoned_serde"))]
+        end: Zoned,
+    },
 
     /// If provided, this property indicates that the operation's security is resistant to quantum
     /// computing attacks.
+    ///
+    /// Quantum resistance is an addition to, not a replacement for, classical security.  A
+    /// construction reporting this [`Rune`] must also provide its reported [`Rune::SecurityBits`]
+    /// against classical attacks.  Hybrid constructions, which combine a classical and a
+    /// post-quantum algorithm so that breaking either alone is insufficient, meet this naturally,
+    /// and therefore satisfy both classical requests and requests for quantum resistance.
+    /// Classical-only constructions never satisfy a request for quantum resistance, and if the
+    /// registry contains nothing else, the request is rejected with this `Rune` among the unmet
+    /// requirements (see [`crate::construction::ConstructionRegistry::select`]).
     QuantumResistance,
 
     /// Resistance to side channel attacks that can be exploited through software, such as timing
@@ -190,13 +227,241 @@ pub enum Rune {
     ///
     /// If used in a [`Provider::forge`] request, only one of the certifications need be provided by
     /// that available Sigaldry environment.  If none of the listed certifications are available,
-    /// the request will be rejected.
+    /// the request will be rejected.  A certification is provided by a certification under the
+    /// same scheme at the same or a higher level.
     Certifications(Vec<SecurityCertification>),
 
-    VariationStrategy(VariationStrategy)
+    /// Specifies how the variation parameter (nonce, IV or tweak) of each seal operation is
+    /// chosen.  See [`VariationStrategy`].
+    ///
+    /// If omitted in forge requests, the variation strategy defaults to
+    /// [`VariationStrategy::Automatic`], so constructions that require caller-provided
+    /// variations are only selected for callers who state that they can provide them.
+    VariationStrategy(VariationStrategy),
+
+    /// If provided, every operation performed with the [`crate::provider::BindRune`] is recorded
+    /// by the provider in an audit log that can be retrieved with
+    /// [`crate::provider::BindRune::audit_log`].
+    ///
+    /// Secure hardware keeps the log in storage the caller cannot modify.  Software providers may
+    /// satisfy this [`Rune`] with an in-memory log, which offers no protection against a
+    /// compromised host and does not survive the process.
+    AuditLogged,
+
+    /// If provided, the [`crate::provider::BindRune`] is bound to the specified application
+    /// domain, such as `"email"` or `"files"`.  The domain is fixed at forge time and the provider
+    /// mixes it into the variation and associated data derivation of every operation, so data
+    /// sealed under one domain cannot be unsealed under another, even by a `BindRune` using the
+    /// same key material.
+    ///
+    /// Unlike associated data, which the caller supplies per operation, the domain is a property
+    /// of the `BindRune` and cannot be forgotten or varied by the caller.  In a
+    /// [`Provider::forge`] request it is satisfied only by a construction bound to the same domain.
+    DomainSeparation(String),
+
+    /// Reports where the key material of the [`crate::provider::BindRune`] came from.  Keys
+    /// created by [`Provider::forge`] are [`KeyOrigin::Generated`], keys imported with
+    /// [`crate::provider::Provider::import_raw`] are [`KeyOrigin::Imported`].
+    ///
+    /// If used in a [`Provider::forge`] request, the key origin must match exactly, so requiring
+    /// [`KeyOrigin::Generated`] excludes keys whose material may exist outside the provider.
+    KeyOrigin(KeyOrigin),
+
+    /// Specifies whether the key material of the [`crate::provider::BindRune`] must, may or must
+    /// not be escrowed, i.e. exported under wrapping so it can be recovered.  Forged `BindRune`s
+    /// report the policy that was requested, and constructions report
+    /// [`EscrowPolicy::Allowed`] if the provider can enforce either policy.
+    ///
+    /// Escrow and [`crate::provider::BindRune::export_wrapped`] are in tension: escrow requires
+    /// that the key can leave the provider, while signing keys often must never leave it so that
+    /// signatures are attributable to the device.  A `BindRune` with [`EscrowPolicy::Forbidden`]
+    /// rejects every export path, so losing the provider means losing the key.
+    Escrow(EscrowPolicy),
+
+    /// This [`Rune`] indicates the length, in bits, of the authentication tag or digest produced
+    /// by the MAC or AEAD component of the construction.  When provided in a [`Provider::forge`]
+    /// request, it specifies the minimum acceptable length.
+    ///
+    /// This is distinct from [`Rune::SecurityBits`].  Some protocols require long tags regardless
+    /// of the strength of the underlying algorithm, and a construction with 128 bits of security
+    /// may well produce a 256-bit tag.  Conversely, truncated tags reduce forgery resistance
+    /// without reducing the security bits of the key.
+    MinTagBits(u16),
+
+    /// Restricts the [`crate::provider::BindRune`] to the listed uses, in the manner of X.509
+    /// key usage.  A key forged for signing must not be usable for encryption, and vice versa.
+    /// Forged `BindRune`s report the usages that were requested, and every operation fails with
+    /// [`Error::KeyUsageViolation`] if its usage is not listed.  See [`Schema::check_usage`].
+    ///
+    /// If used in a [`Provider::forge`] request, the construction must support all of the listed
+    /// usages.  If the [`Rune`] is absent, the `BindRune` is unrestricted.
+    KeyUsage(Vec<KeyUsage>),
+
+    /// If provided, the [`crate::provider::BindRune`] protects against rollback, as required for
+    /// firmware and update signing keys.  Each seal operation is bound to a version with
+    /// [`crate::provider::Operation::set_version`], and the provider refuses to seal a version
+    /// lower than the highest it has already sealed.
+    ///
+    /// The provider must keep the high-water mark in monotonic storage that cannot be reset,
+    /// rolled back or cloned along with the key, such as a hardware monotonic counter or
+    /// replay-protected memory.  Software providers that keep the mark in ordinary storage offer
+    /// no protection against an attacker who can restore an old copy of it, and should not report
+    /// this [`Rune`].
+    AntiRollback,
+
+    /// If provided, the implementation of the construction, including any hardware or firmware
+    /// it runs on, is open to and has been subject to independent review, rather than being a
+    /// black box.  This is a property of the development process, not a cryptographic one: it
+    /// says nothing about the strength of the construction, only that claims about it can be
+    /// checked.
+    OpenImplementation,
+
+    /// If provided, the construction is a key-committing AEAD: a ciphertext can be unsealed
+    /// successfully under at most one key.  Common AEADs such as AES-GCM and ChaCha20-Poly1305
+    /// are not committing, because an attacker who knows several keys can craft a single
+    /// ciphertext that is valid under all of them.
+    ///
+    /// That matters wherever a ciphertext may be tried against more than one key, such as with
+    /// password-derived keys or multi-recipient messages.  In a partitioning oracle attack, the
+    /// attacker submits such multi-key ciphertexts and learns from each success or failure which
+    /// partition of the candidate keys contains the right one, recovering the key in a
+    /// logarithmic number of queries.  A committing construction leaves only one candidate per
+    /// query.
+    #[cfg_attr(feature = "serde", serde(alias = "KeyCommitting"))]
+    KeyCommitment,
+
+    /// Reports the number of derivation steps between the key of the
+    /// [`crate::provider::BindRune`] and its hardware-protected root, in a hierarchical
+    /// deterministic key system.  Root keys have depth zero, and
+    /// [`crate::provider::BindRune::derive_subkey`] stamps each subkey with its parent's depth
+    /// plus one.
+    ///
+    /// If used in a [`Provider::forge`] or `derive_subkey` request, the value is the maximum
+    /// acceptable depth, and deriving a subkey beyond it fails with
+    /// [`Error::DerivationTooDeep`].
+    DerivationDepth(u8),
+
+    /// If provided, the provider can produce a signed, verifiable count of the operations
+    /// performed with the [`crate::provider::BindRune`], with
+    /// [`crate::provider::BindRune::usage_proof`].  Unlike the remaining counts reported in the
+    /// `Enforced*` runes, which are advisory, a usage proof can be checked by a third party such
+    /// as an auditor.
+    UsageProof,
+
+    /// If provided in a [`Provider::forge`] request, this property caps the number of messages
+    /// the key may be able to process, e.g. to limit the blast radius of a key compromise.
+    ///
+    /// Note that this is the inverse of [`Rune::MessageLimit`] and [`Rune::EnforcedMessageLimit`],
+    /// which request a _minimum_.  A capability satisfies this rune only if the message limit it
+    /// reports, enforced or not, is at most the cap, so a construction with a limit of 2⁶⁴
+    /// messages fails a cap of 2³² unless the provider enforces a lower limit.  It is never
+    /// returned from [`crate::provider::BindRune::schema`], which reports the enforced limit
+    /// instead.
+    MaxEnforcedMessageLimit(u128),
+
+    /// If provided, this property indicates that the construction provides forward secrecy:
+    /// compromise of long-term key material does not expose previously sealed messages, because
+    /// each message or session is protected with ephemeral keys that are destroyed after use.
+    ForwardSecrecy,
+
+    /// If provided, this property indicates that the construction is nonce-misuse resistant, as
+    /// AES-GCM-SIV is: repeating a variation parameter reveals only whether the same message was
+    /// sealed twice, rather than compromising confidentiality or authenticity.  Constructions
+    /// with large random nonces, such as XChaCha20-Poly1305, make repetition negligibly likely
+    /// instead, and may also report it.
+    ///
+    /// This reduces, but does not eliminate, the concerns addressed by [`Rune::MessageLimit`].
+    /// Misuse-resistant constructions still have message and data limits, beyond which their
+    /// security degrades, and those limits are enforced as usual.
+    NonceMisuseResistance,
+
+    /// If provided, the provider can produce signed evidence, in the specified format, that the
+    /// key of the [`crate::provider::BindRune`] was generated inside, and never left, certified
+    /// hardware with the properties reported in its schema.  The evidence itself is produced by
+    /// [`crate::provider::BindRune::attest`].
+    ///
+    /// If used in a [`Provider::forge`] request, the requirement is met only by a construction
+    /// that reports the same format.
+    Attestation(AttestationFormat),
+
+    /// The maximum lifetime of the key of the [`crate::provider::BindRune`], after which it must
+    /// be rotated, i.e. replaced by a newly forged key.
+    ///
+    /// If used in a [`Provider::forge`] request, the requirement is met by a reported interval no
+    /// longer than the requested one, i.e. by rotating at least as often.  The interval is stored
+    /// as a [`SignedDuration`] rather than a [`Span`] so that runes can be compared; see
+    /// [`SchemaBuilder::key_rotation_interval`].
+    KeyRotationInterval(#[cfg_attr(feature = "defmt", defmt(Display2Format))] SignedDuration),
+
+    /// The source of the randomness from which the key material of the
+    /// [`crate::provider::BindRune`] was generated.
+    ///
+    /// If used in a [`Provider::forge`] request, the requirement is met by the requested source or
+    /// a stronger one, in the order given by [`EntropySource`].  Keys that are imported rather
+    /// than generated report [`EntropySource::Unspecified`].
+    EntropySource(EntropySource),
+
+    /// If provided, this property indicates that the construction is deterministic: the same key
+    /// and input always produce the same output, e.g. AES-SIV used without a nonce for
+    /// deduplication or convergent encryption, or ECDSA with RFC 6979 nonces.  Deterministic
+    /// encryption reveals whether two messages are equal, so it should only be requested where
+    /// that is acceptable.
+    ///
+    /// Deterministic constructions don't generate a random variation.  Under
+    /// [`VariationStrategy::Automatic`] they return no variation parameter, and under
+    /// [`VariationStrategy::CallerProvided`] the output is deterministic for each variation the
+    /// caller supplies.  Deterministic encryption is inherently [`Rune::NonceMisuseResistance`],
+    /// since it is secure when the same variation is used for every message, but the converse
+    /// does not hold: a misuse-resistant construction given a random nonce is not deterministic.
+    Deterministic,
+
+    /// If provided, this property indicates that the construction includes a memory-hard
+    /// password-based KDF, such as Argon2id or scrypt, with at least the specified costs.  See
+    /// [`crate::kdf::PasswordKdf`].
+    ///
+    /// If used in a [`Provider::forge`] request, the requirement is met by a construction whose
+    /// memory and iterations are both at least those requested.  Parallelism is a performance
+    /// setting rather than a security one and is not compared.
+    MemoryHardness(CostParameters),
+
+    /// If provided, this property indicates that the construction's key agreement has
+    /// contributory behavior: it rejects peer public keys, such as low-order or identity points,
+    /// that would force the shared secret to a value one party controls.  See
+    /// [`crate::key_agreement::KeyAgreement::contributory_behavior`].
+    ContributoryKeyAgreement,
+
+    /// If provided, this property indicates that the construction can encrypt a single payload
+    /// to up to `max` recipients, with
+    /// [`crate::hybrid_encryption::HybridEncrypt::seal_multi`].
+    ///
+    /// If used in a [`Provider::forge`] request, the requirement is met by a construction
+    /// supporting at least `max` recipients.
+    MultiRecipient { max: u32 },
+
+    /// If provided, this property indicates that the key is split among `n` parties, any `t` of
+    /// whom must cooperate to use it, as in threshold signing or other multi-party computation
+    /// schemes.  No coalition of fewer than `t` parties learns anything about the key.
+    ///
+    /// If used in a [`Provider::forge`] request, the requirement is met by a construction with at
+    /// least `n` parties, so that it is at least as available, and a quorum of at most `t`, so
+    /// that a quorum is no harder to reach.
+    Threshold { t: u16, n: u16 },
+
+    /// A rune defined outside this crate, by a downstream crate that needs to express properties
+    /// of its own construction families.  `id` identifies the rune, and must be assigned so that
+    /// it doesn't collide with other extensions in use; `value` is its encoded payload.
+    ///
+    /// By default, an extension requirement is met only by an extension with the same `id` and an
+    /// identical `value`.  Other comparison rules can be registered in [`RuneComparators`] and
+    /// applied with [`Schema::satisfies_with`] and [`Schema::coverage_with`], or registered with
+    /// [`crate::construction::ConstructionRegistry::register_comparator`] to apply them when
+    /// selecting constructions.
+    Extension { id: u16, value: Vec<u8> },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum VariationStrategy {
     /// The variation is automatically generated by the [`seal`](`crate::provider::BindRune::seal`)
     /// operation and returned to the caller in the [`crate::provider::OperationResult`].  This is
@@ -215,10 +480,85 @@ pub enum VariationStrategy {
     CallerProvided(VariationType),
 }
 
+/// Runes are ordered by [`Rune::variant_index`] and then by payload, so that collections of runes
+/// sort the same way as the runes of a [`Schema`].  Of a requested and an enforced limit with the
+/// same index, the requested limit sorts first.  Numeric payloads and time horizons are compared
+/// by value, and vector payloads lexicographically.
+impl Ord for Rune {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let is_enforced = |rune: &Rune| {
+            matches!(
+                rune,
+                Rune::EnforcedMessageLimit(_)
+                    | Rune::EnforcedMessageSizeLimit(_)
+                    | Rune::EnforcedTotalDataLimit(_)
+            )
+        };
+        self.variant_index()
+            .cmp(&other.variant_index())
+            .then_with(|| is_enforced(self).cmp(&is_enforced(other)))
+            .then_with(|| match (self, other) {
+                (Rune::SecurityBits(a), Rune::SecurityBits(b)) => a.cmp(b),
+                (Rune::Confidentiality { end_time: a }, Rune::Confidentiality { end_time: b }) => {
+                    a.cmp(b)
+                }
+                (Rune::Integrity { year: a }, Rune::Integrity { year: b }) => a.cmp(b),
+                (
+                    Rune::Authentication { origin: a_origin, year: a },
+                    Rune::Authentication { origin: b_origin, year: b },
+                ) => a_origin.cmp(b_origin).then(a.cmp(b)),
+                (
+                    Rune::CryptoPeriod { begin: a_begin, end: a_end },
+                    Rune::CryptoPeriod { begin: b_begin, end: b_end },
+                ) => a_begin.cmp(b_begin).then_with(|| a_end.cmp(b_end)),
+                (
+                    Rune::SoftwareSideChannelResistance(a),
+                    Rune::SoftwareSideChannelResistance(b),
+                ) => a.cmp(b),
+                (
+                    Rune::HardwareSideChannelResistance(a),
+                    Rune::HardwareSideChannelResistance(b),
+                ) => a.cmp(b),
+                (Rune::Isolated(a), Rune::Isolated(b)) => a.cmp(b),
+                (Rune::Certifications(a), Rune::Certifications(b)) => a.cmp(b),
+                (Rune::VariationStrategy(a), Rune::VariationStrategy(b)) => a.cmp(b),
+                (Rune::DomainSeparation(a), Rune::DomainSeparation(b)) => a.cmp(b),
+                (Rune::KeyOrigin(a), Rune::KeyOrigin(b)) => a.cmp(b),
+                (Rune::Attestation(a), Rune::Attestation(b)) => a.cmp(b),
+                (Rune::Escrow(a), Rune::Escrow(b)) => a.cmp(b),
+                (Rune::MinTagBits(a), Rune::MinTagBits(b)) => a.cmp(b),
+                (Rune::KeyUsage(a), Rune::KeyUsage(b)) => a.cmp(b),
+                (Rune::DerivationDepth(a), Rune::DerivationDepth(b)) => a.cmp(b),
+                (Rune::KeyRotationInterval(a), Rune::KeyRotationInterval(b)) => a.cmp(b),
+                (Rune::EntropySource(a), Rune::EntropySource(b)) => a.cmp(b),
+                (Rune::MemoryHardness(a), Rune::MemoryHardness(b)) => a.cmp(b),
+                (Rune::MultiRecipient { max: a }, Rune::MultiRecipient { max: b }) => a.cmp(b),
+                (Rune::Threshold { t: a_t, n: a_n }, Rune::Threshold { t: b_t, n: b_n }) => {
+                    a_t.cmp(b_t).then(a_n.cmp(b_n))
+                }
+                (Rune::MaxEnforcedMessageLimit(a), Rune::MaxEnforcedMessageLimit(b)) => a.cmp(b),
+                (Rune::Extension { value: a, .. }, Rune::Extension { value: b, .. }) => a.cmp(b),
+                // Limits of the same kind, or unit runes.
+                _ => self.limit().cmp(&other.limit()),
+            })
+    }
+}
+
+impl PartialOrd for Rune {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The variant index of [`Rune::Extension`] with `id` zero.  Extensions sort after all built-in
+/// runes, in order of `id`.
+const EXTENSION_INDEX_BASE: u32 = 1 << 16;
+
 impl Rune {
     /// Returns a numeric index for the variant, used for ordering by
-    /// discriminant.
-    fn variant_index(&self) -> u32 {
+    /// discriminant.  Requested and enforced limits share an index, since a schema contains at
+    /// most one of each pair.
+    pub fn variant_index(&self) -> u32 {
         match self {
             Rune::PublicPrivateKeyPair => 0,
             Rune::SecurityBits(_) => 1,
@@ -235,6 +575,467 @@ impl Rune {
             Rune::Isolated(_) => 15,
             Rune::Certifications(_) => 16,
             Rune::VariationStrategy(_) => 17,
+            Rune::AuditLogged => 18,
+            Rune::DomainSeparation(_) => 19,
+            Rune::KeyOrigin(_) => 20,
+            Rune::Escrow(_) => 21,
+            Rune::MinTagBits(_) => 22,
+            Rune::KeyUsage(_) => 23,
+            Rune::AntiRollback => 24,
+            Rune::OpenImplementation => 25,
+            Rune::KeyCommitment => 26,
+            Rune::DerivationDepth(_) => 27,
+            Rune::UsageProof => 28,
+            Rune::MaxEnforcedMessageLimit(_) => 29,
+            Rune::ForwardSecrecy => 30,
+            Rune::NonceMisuseResistance => 31,
+            Rune::Attestation(_) => 32,
+            Rune::KeyRotationInterval(_) => 33,
+            Rune::EntropySource(_) => 34,
+            Rune::Deterministic => 35,
+            Rune::MemoryHardness(_) => 36,
+            Rune::ContributoryKeyAgreement => 37,
+            Rune::MultiRecipient { .. } => 38,
+            Rune::Threshold { .. } => 39,
+            Rune::Extension { id, .. } => EXTENSION_INDEX_BASE + u32::from(*id),
+        }
+    }
+
+    /// Returns the variant index at which capabilities report the value this rune constrains.
+    /// This is the rune's own index, except for caps on limits, which are checked against the
+    /// reported limit.
+    fn reported_index(&self) -> u32 {
+        match self {
+            Rune::MaxEnforcedMessageLimit(_) => Rune::MessageLimit(0).variant_index(),
+            _ => self.variant_index(),
+        }
+    }
+
+    /// Returns the rune in canonical form, with vector payloads sorted and deduplicated.
+    fn canonical(mut self) -> Self {
+        match &mut self {
+            Rune::SoftwareSideChannelResistance(items) => canonicalize(items),
+            Rune::HardwareSideChannelResistance(items) => canonicalize(items),
+            Rune::Certifications(items) => canonicalize(items),
+            Rune::KeyUsage(items) => canonicalize(items),
+            _ => {}
+        }
+        self
+    }
+
+    fn is_canonical(&self) -> bool {
+        match self {
+            Rune::SoftwareSideChannelResistance(items) => is_canonical(items),
+            Rune::HardwareSideChannelResistance(items) => is_canonical(items),
+            Rune::Certifications(items) => is_canonical(items),
+            Rune::KeyUsage(items) => is_canonical(items),
+            _ => true,
+        }
+    }
+
+    /// Returns the limit value of a limit [`Rune`], whether requested or enforced.
+    pub(crate) fn limit(&self) -> Option<u128> {
+        match self {
+            Rune::MessageLimit(limit)
+            | Rune::EnforcedMessageLimit(limit)
+            | Rune::MessageSizeLimit(limit)
+            | Rune::EnforcedMessageSizeLimit(limit)
+            | Rune::TotalDataLimit(limit)
+            | Rune::EnforcedTotalDataLimit(limit) => Some(*limit),
+            _ => None,
+        }
+    }
+
+    /// Returns the numeric value of runes that are a single quantity, such as
+    /// [`Rune::SecurityBits`] and the limits, for [`Schema::diff`].
+    fn magnitude(&self) -> Option<u128> {
+        match self {
+            Rune::SecurityBits(bits) => Some(u128::from(*bits)),
+            Rune::MinTagBits(bits) => Some(u128::from(*bits)),
+            Rune::DerivationDepth(depth) => Some(u128::from(*depth)),
+            Rune::MaxEnforcedMessageLimit(limit) => Some(*limit),
+            _ => self.limit(),
+        }
+    }
+
+    /// Assesses how well `provided`, the rune reported at the same variant index by a
+    /// construction or [`crate::provider::BindRune`], meets this rune as a requirement.
+    fn coverage_by(&self, provided: Option<&Rune>) -> Coverage {
+        let Some(provided) = provided else {
+            return Coverage::Unmet;
+        };
+        match (self, provided) {
+            (Rune::PublicPrivateKeyPair, Rune::PublicPrivateKeyPair)
+            | (Rune::QuantumResistance, Rune::QuantumResistance)
+            | (Rune::AuditLogged, Rune::AuditLogged)
+            | (Rune::AntiRollback, Rune::AntiRollback)
+            | (Rune::OpenImplementation, Rune::OpenImplementation)
+            | (Rune::KeyCommitment, Rune::KeyCommitment)
+            | (Rune::UsageProof, Rune::UsageProof)
+            | (Rune::ForwardSecrecy, Rune::ForwardSecrecy)
+            | (Rune::NonceMisuseResistance, Rune::NonceMisuseResistance)
+            | (Rune::Deterministic, Rune::Deterministic)
+            | (Rune::ContributoryKeyAgreement, Rune::ContributoryKeyAgreement) => Coverage::Met,
+            (Rune::SecurityBits(required), Rune::SecurityBits(provided)) => {
+                Coverage::compare(provided, required)
+            }
+            (
+                Rune::MessageLimit(_)
+                | Rune::EnforcedMessageLimit(_)
+                | Rune::MessageSizeLimit(_)
+                | Rune::EnforcedMessageSizeLimit(_)
+                | Rune::TotalDataLimit(_)
+                | Rune::EnforcedTotalDataLimit(_),
+                _,
+            ) => match (self.limit(), provided.limit()) {
+                (Some(required), Some(provided)) => Coverage::compare(&provided, &required),
+                _ => Coverage::Unmet,
+            },
+            (Rune::MaxEnforcedMessageLimit(cap), _) => match provided.limit() {
+                Some(provided) if provided <= *cap => Coverage::Met,
+                _ => Coverage::Unmet,
+            },
+            (
+                Rune::Confidentiality { end_time: required },
+                Rune::Confidentiality { end_time: provided },
+            ) => Coverage::compare(provided, required),
+            (Rune::Integrity { year: required }, Rune::Integrity { year: provided }) => {
+                Coverage::compare(provided, required)
+            }
+            (
+                Rune::Authentication { origin: required_origin, year: required },
+                Rune::Authentication { origin: provided_origin, year: provided },
+            ) if required_origin == provided_origin => Coverage::compare(provided, required),
+            (
+                Rune::CryptoPeriod { begin: required_begin, end: required_end },
+                Rune::CryptoPeriod { begin: provided_begin, end: provided_end },
+            ) => {
+                if provided_begin > required_begin || provided_end < required_end {
+                    Coverage::Unmet
+                } else if provided_begin == required_begin && provided_end == required_end {
+                    Coverage::Met
+                } else {
+                    Coverage::Exceeded
+                }
+            }
+            (
+                Rune::SoftwareSideChannelResistance(required),
+                Rune::SoftwareSideChannelResistance(provided),
+            ) => Coverage::superset(provided, required),
+            (
+                Rune::HardwareSideChannelResistance(required),
+                Rune::HardwareSideChannelResistance(provided),
+            ) => Coverage::superset(provided, required),
+            (Rune::KeyUsage(required), Rune::KeyUsage(provided)) => {
+                Coverage::superset(provided, required)
+            }
+            (Rune::DomainSeparation(required), Rune::DomainSeparation(provided)) => {
+                Coverage::exact(provided, required)
+            }
+            (Rune::Extension { value: required, .. }, Rune::Extension { value: provided, .. }) => {
+                Coverage::exact(provided, required)
+            }
+            (Rune::Attestation(required), Rune::Attestation(provided)) => {
+                Coverage::exact(provided, required)
+            }
+            (Rune::KeyOrigin(required), Rune::KeyOrigin(provided)) => {
+                Coverage::exact(provided, required)
+            }
+            (Rune::Escrow(required), Rune::Escrow(provided)) => {
+                if *required == EscrowPolicy::Allowed || *provided == EscrowPolicy::Allowed {
+                    Coverage::Met
+                } else {
+                    Coverage::exact(provided, required)
+                }
+            }
+            (Rune::MinTagBits(required), Rune::MinTagBits(provided)) => {
+                Coverage::compare(provided, required)
+            }
+            (Rune::DerivationDepth(required), Rune::DerivationDepth(provided)) => {
+                // Shallower keys are better, so the comparison is reversed.
+                Coverage::compare(required, provided)
+            }
+            (Rune::KeyRotationInterval(required), Rune::KeyRotationInterval(provided)) => {
+                // Shorter intervals are better, so the comparison is reversed.
+                Coverage::compare(required, provided)
+            }
+            (Rune::Isolated(required), Rune::Isolated(provided)) => {
+                Coverage::compare(provided, required)
+            }
+            (Rune::EntropySource(required), Rune::EntropySource(provided)) => {
+                Coverage::compare(provided, required)
+            }
+            (Rune::MultiRecipient { max: required }, Rune::MultiRecipient { max: provided }) => {
+                Coverage::compare(provided, required)
+            }
+            (
+                Rune::Threshold { t: required_t, n: required_n },
+                Rune::Threshold { t: provided_t, n: provided_n },
+            ) => {
+                if provided_n < required_n || provided_t > required_t {
+                    Coverage::Unmet
+                } else if provided_n == required_n && provided_t == required_t {
+                    Coverage::Met
+                } else {
+                    Coverage::Exceeded
+                }
+            }
+            (Rune::MemoryHardness(required), Rune::MemoryHardness(provided)) => {
+                let costs = |params: &CostParameters| (params.memory_kib, params.iterations);
+                let (provided, required) = (costs(provided), costs(required));
+                if provided.0 < required.0 || provided.1 < required.1 {
+                    Coverage::Unmet
+                } else if provided == required {
+                    Coverage::Met
+                } else {
+                    Coverage::Exceeded
+                }
+            }
+            (Rune::Certifications(required), Rune::Certifications(provided)) => {
+                // Only one of the requested certifications need be provided.
+                if required.is_empty()
+                    || required.iter().any(|cert| provided.iter().any(|p| p.meets(cert)))
+                {
+                    Coverage::Met
+                } else {
+                    Coverage::Unmet
+                }
+            }
+            (Rune::VariationStrategy(required), Rune::VariationStrategy(provided)) => {
+                if required.accepts(provided) { Coverage::Met } else { Coverage::Unmet }
+            }
+            _ => Coverage::Unmet,
+        }
+    }
+
+    /// Returns the stricter of two requirement runes at the same variant index, i.e. one that is
+    /// satisfied only by capabilities that satisfy both, failing with
+    /// [`Error::ConflictingRequirements`] where no rune can be.  See [`Schema::merge`] for the
+    /// rules.
+    fn stricter(&self, other: &Rune) -> Result<Rune> {
+        let conflict = || {
+            Err(Error::ConflictingRequirements(format!("{:?} conflicts with {:?}", self, other)))
+        };
+        Ok(match (self, other) {
+            (Rune::Escrow(EscrowPolicy::Allowed), Rune::Escrow(_)) => other.clone(),
+            (Rune::Escrow(a), Rune::Escrow(b)) if a != b && *b != EscrowPolicy::Allowed => {
+                return conflict();
+            }
+            (Rune::Escrow(_), Rune::Escrow(_)) => self.clone(),
+            (Rune::VariationStrategy(_), Rune::VariationStrategy(VariationStrategy::Automatic)) => {
+                other.clone()
+            }
+            (
+                Rune::VariationStrategy(VariationStrategy::CallerProvided(a)),
+                Rune::VariationStrategy(VariationStrategy::CallerProvided(b)),
+            ) if a != b => {
+                Rune::VariationStrategy(VariationStrategy::CallerProvided(VariationType::Arbitrary))
+            }
+            (Rune::VariationStrategy(_), Rune::VariationStrategy(_)) => self.clone(),
+            (Rune::Authentication { origin: a, .. }, Rune::Authentication { origin: b, .. })
+                if a != b =>
+            {
+                return conflict();
+            }
+            (Rune::Certifications(a), _) if a.is_empty() => other.clone(),
+            (_, Rune::Certifications(b)) if b.is_empty() => self.clone(),
+            (Rune::Certifications(a), Rune::Certifications(b)) => {
+                let common: Vec<_> = a.iter().filter(|cert| b.contains(cert)).cloned().collect();
+                if common.is_empty() {
+                    return conflict();
+                }
+                Rune::Certifications(common)
+            }
+            (Rune::KeyUsage(a), Rune::KeyUsage(b)) => {
+                let common: Vec<_> = a.iter().filter(|usage| b.contains(usage)).cloned().collect();
+                if common.is_empty() {
+                    return conflict();
+                }
+                Rune::KeyUsage(common)
+            }
+            (
+                Rune::DomainSeparation(_) | Rune::KeyOrigin(_) | Rune::Attestation(_),
+                Rune::DomainSeparation(_) | Rune::KeyOrigin(_) | Rune::Attestation(_),
+            ) if self != other => return conflict(),
+            (Rune::MaxEnforcedMessageLimit(a), Rune::MaxEnforcedMessageLimit(b)) => {
+                Rune::MaxEnforcedMessageLimit(*a.min(b))
+            }
+            (
+                Rune::EnforcedMessageLimit(_)
+                | Rune::EnforcedMessageSizeLimit(_)
+                | Rune::EnforcedTotalDataLimit(_),
+                Rune::EnforcedMessageLimit(_)
+                | Rune::EnforcedMessageSizeLimit(_)
+                | Rune::EnforcedTotalDataLimit(_),
+            ) => match (self.limit(), other.limit()) {
+                (Some(a), Some(b)) if b < a => other.clone(),
+                _ => self.clone(),
+            },
+            (
+                Rune::EnforcedMessageLimit(_)
+                | Rune::EnforcedMessageSizeLimit(_)
+                | Rune::EnforcedTotalDataLimit(_),
+                _,
+            ) => self.clone(),
+            (
+                _,
+                Rune::EnforcedMessageLimit(_)
+                | Rune::EnforcedMessageSizeLimit(_)
+                | Rune::EnforcedTotalDataLimit(_),
+            ) => other.clone(),
+            (
+                Rune::DomainSeparation(_)
+                | Rune::KeyOrigin(_)
+                | Rune::Attestation(_)
+                | Rune::PublicPrivateKeyPair
+                | Rune::QuantumResistance
+                | Rune::AuditLogged
+                | Rune::AntiRollback
+                | Rune::OpenImplementation
+                | Rune::KeyCommitment
+                | Rune::UsageProof
+                | Rune::ForwardSecrecy
+                | Rune::NonceMisuseResistance
+                | Rune::Deterministic
+                | Rune::ContributoryKeyAgreement,
+                _,
+            ) => self.clone(),
+            _ => self.stronger(other),
+        })
+    }
+
+    /// Returns the stronger of two capability runes at the same variant index, combining them
+    /// where neither dominates the other (e.g. the union of side channel resistances).
+    fn stronger(&self, other: &Rune) -> Rune {
+        match (self, other) {
+            (Rune::SecurityBits(a), Rune::SecurityBits(b)) => Rune::SecurityBits(*a.max(b)),
+            (Rune::Confidentiality { end_time: a }, Rune::Confidentiality { end_time: b }) => {
+                Rune::Confidentiality { end_time: *a.max(b) }
+            }
+            (Rune::Integrity { year: a }, Rune::Integrity { year: b }) => {
+                Rune::Integrity { year: *a.max(b) }
+            }
+            (Rune::Authentication { year: a, .. }, Rune::Authentication { year: b, .. }) => {
+                if b > a { other.clone() } else { self.clone() }
+            }
+            (
+                Rune::CryptoPeriod { begin: a_begin, end: a_end },
+                Rune::CryptoPeriod { begin: b_begin, end: b_end },
+            ) => Rune::CryptoPeriod {
+                begin: a_begin.min(b_begin).clone(),
+                end: a_end.max(b_end).clone(),
+            },
+            (Rune::SoftwareSideChannelResistance(a), Rune::SoftwareSideChannelResistance(b)) => {
+                Rune::SoftwareSideChannelResistance(union(a, b))
+            }
+            (Rune::HardwareSideChannelResistance(a), Rune::HardwareSideChannelResistance(b)) => {
+                Rune::HardwareSideChannelResistance(union(a, b))
+            }
+            (Rune::Isolated(a), Rune::Isolated(b)) => Rune::Isolated(*a.max(b)),
+            (Rune::EntropySource(a), Rune::EntropySource(b)) => Rune::EntropySource(*a.max(b)),
+            (Rune::MultiRecipient { max: a }, Rune::MultiRecipient { max: b }) => {
+                Rune::MultiRecipient { max: *a.max(b) }
+            }
+            (Rune::Threshold { t: a_t, n: a_n }, Rune::Threshold { t: b_t, n: b_n }) => {
+                Rune::Threshold { t: *a_t.min(b_t), n: *a_n.max(b_n) }
+            }
+            (Rune::MemoryHardness(a), Rune::MemoryHardness(b)) => {
+                Rune::MemoryHardness(CostParameters {
+                    memory_kib: a.memory_kib.max(b.memory_kib),
+                    iterations: a.iterations.max(b.iterations),
+                    parallelism: a.parallelism,
+                })
+            }
+            (Rune::DerivationDepth(a), Rune::DerivationDepth(b)) => {
+                Rune::DerivationDepth(*a.min(b))
+            }
+            (Rune::KeyRotationInterval(a), Rune::KeyRotationInterval(b)) => {
+                Rune::KeyRotationInterval(*a.min(b))
+            }
+            (Rune::MinTagBits(a), Rune::MinTagBits(b)) => Rune::MinTagBits(*a.max(b)),
+            (Rune::Escrow(_), Rune::Escrow(EscrowPolicy::Allowed)) => other.clone(),
+            (Rune::Certifications(a), Rune::Certifications(b)) => Rune::Certifications(union(a, b)),
+            (Rune::KeyUsage(a), Rune::KeyUsage(b)) => Rune::KeyUsage(union(a, b)),
+            (Rune::VariationStrategy(a), Rune::VariationStrategy(b)) => {
+                // The strategy that places the smallest burden on callers is the most capable.
+                if a.accepts(b) { other.clone() } else { self.clone() }
+            }
+            _ => match (self.limit(), other.limit()) {
+                (Some(a), Some(b)) if b > a => other.clone(),
+                _ => self.clone(),
+            },
+        }
+    }
+}
+
+fn canonicalize<T: Ord>(items: &mut Vec<T>) {
+    items.sort();
+    items.dedup();
+}
+
+fn is_canonical<T: Ord>(items: &[T]) -> bool {
+    items.windows(2).all(|pair| pair[0] < pair[1])
+}
+
+/// Returns the last instant of `year`.
+fn end_of_year(year: u16) -> Result<DateTime> {
+    Ok(DateTime::new(saturating_year(year), 12, 31, 23, 59, 59, 999_999_999)?)
+}
+
+/// Converts a year stored as `u16` to the `i16` used by `jiff`, saturating.
+fn saturating_year(year: u16) -> i16 {
+    i16::try_from(year).unwrap_or(i16::MAX)
+}
+
+/// Returns the last year in which `security_bits` bits of security are acceptable, per NIST SP
+/// 800-57 Part 1, table 4, or `None` if there is no limit.
+fn security_bits_last_year(security_bits: u8) -> Option<i16> {
+    match security_bits {
+        0..112 => Some(2010),
+        112..128 => Some(2030),
+        _ => None,
+    }
+}
+
+/// Writes a limit for [`Rune`]'s `Display` implementation, rendering 2¹²⁸ as `unbounded`.
+fn write_limit(f: &mut fmt::Formatter<'_>, name: &str, limit: u128) -> fmt::Result {
+    if limit == u128::MAX {
+        write!(f, "{}: unbounded", name)
+    } else {
+        write!(f, "{}: {}", name, limit)
+    }
+}
+
+/// Writes a list of items for [`Rune`]'s `Display` implementation.
+fn write_list<T: fmt::Debug>(f: &mut fmt::Formatter<'_>, name: &str, items: &[T]) -> fmt::Result {
+    write!(f, "{}:", name)?;
+    for (i, item) in items.iter().enumerate() {
+        write!(f, "{} {:?}", if i == 0 { "" } else { "," }, item)?;
+    }
+    Ok(())
+}
+
+/// Returns the items of `a` followed by the items of `b` not already in `a`.
+fn union<T: PartialEq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut union = a.to_vec();
+    union.extend(b.iter().filter(|item| !a.contains(item)).cloned());
+    union
+}
+
+impl VariationStrategy {
+    /// Returns true if a construction requiring the `provided` strategy can be used by a caller
+    /// who requested this strategy.  [`VariationStrategy::Automatic`] places no burden on the
+    /// caller, so it is always acceptable.  A caller-provided variation is acceptable if the caller
+    /// can provide the required [`VariationType`], or if the construction accepts
+    /// [`VariationType::Arbitrary`] variations.
+    fn accepts(&self, provided: &VariationStrategy) -> bool {
+        match (self, provided) {
+            (_, VariationStrategy::Automatic) => true,
+            (VariationStrategy::Automatic, VariationStrategy::CallerProvided(_)) => false,
+            (
+                VariationStrategy::CallerProvided(available),
+                VariationStrategy::CallerProvided(required),
+            ) => available == required || *required == VariationType::Arbitrary,
         }
     }
 }
@@ -242,7 +1043,9 @@ impl Rune {
 /// Side channel resistances that can be exploited through software attacks, typically by malicious
 /// code running on the same system or by an attacker who can measure timing or other
 /// software-observable characteristics.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SoftwareSideChannelResistance {
     /// The operation is constant time and therefore resistant to timing attacks.
     ConstantTime,
@@ -257,7 +1060,9 @@ pub enum SoftwareSideChannelResistance {
 
 /// Side channel resistances that require physical access to the hardware to exploit, such as power
 /// analysis or electromagnetic emissions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum HardwareSideChannelResistance {
     /// The operation is power analysis resistant (including both differential power analysis and
     /// simple power analysis), meaning that it is resistant to power analysis attacks executed
@@ -274,8 +1079,10 @@ pub enum HardwareSideChannelResistance {
 }
 
 /// The level of isolation provided by the operation, including keys and
-/// computation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// computation.  Levels are ordered from weakest to strongest isolation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum IsolationLevel {
     /// The operation is not isolated, running in the same process as the
     /// application.
@@ -303,205 +1110,2576 @@ pub enum IsolationLevel {
     DiscreteCpu,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct OriginIdentity;
+/// The source of randomness used to generate key material.  See [`Rune::EntropySource`].  Sources
+/// are ordered from weakest to strongest assurance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EntropySource {
+    /// The source of randomness is unknown or not reported.
+    Unspecified,
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SecurityCertification;
+    /// A hardware true random number generator whose entropy source has not been validated.
+    HardwareTrng,
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Schema {
-    runes: Vec<Rune>,
+    /// A deterministic random bit generator conforming to NIST SP 800-90A, seeded from an
+    /// approved entropy source.
+    Drbg800_90A,
+
+    /// A random bit generator whose entropy source has been validated under NIST SP 800-90B.
+    Validated800_90B,
 }
 
-impl Schema {}
+/// The origin of the key material of a [`crate::provider::BindRune`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum KeyOrigin {
+    /// The key material was generated by the provider and has never existed outside of it.
+    Generated,
 
-const DEFAULT_RUNES: [Rune; 3] = [
-    // The default message limit is 2¹⁶.
-    Rune::MessageLimit(2_u128.pow(16)),
-    // The default message size limit is 2¹⁶.
-    Rune::MessageSizeLimit(2_u128.pow(16)),
-    // The default total data limit is 2³².
-    Rune::TotalDataLimit(2_u128.pow(32)),
-];
+    /// The key material was generated elsewhere and imported into the provider.  Copies of it may
+    /// exist outside the provider.
+    Imported,
+}
 
-pub struct SchemaBuilder {
-    runes: BTreeMap<u32, Rune>,
+/// The format of the key attestation evidence a provider can produce.  See [`Rune::Attestation`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AttestationFormat {
+    /// An Android Keystore key attestation certificate chain.
+    AndroidKeystore,
+
+    /// A TPM 2.0 quote, signed by an attestation key.
+    TpmQuote,
+
+    /// A FIDO U2F attestation statement.
+    FidoU2f,
+
+    /// A format not listed here, identified by name.
+    Custom(String),
 }
 
-/// Helper macro to push an item to a vector-valued Rune variant, creating the variant if needed.
-/// Duplicates are filtered out.
-macro_rules! push_to_vec_rune {
-    ($runes:expr, $variant:ident, $item:expr) => {{
-        let item = $item;
-        let rune = Rune::$variant(vec![item]);
-        let index = rune.variant_index();
-        match $runes.insert(index, rune) {
-            Some(Rune::$variant(mut vec)) => {
-                if !vec.contains(&item) {
-                    vec.push(item);
-                }
-                $runes.insert(index, Rune::$variant(vec));
-            }
-            None => { /* Already inserted above */ }
-            _ => unreachable!("Got something other than the rune at its variant index"),
-        }
-    }};
+/// The key escrow policy of a [`crate::provider::BindRune`].  See [`Rune::Escrow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EscrowPolicy {
+    /// The key material must be escrowed, so it can be recovered if the provider is lost.
+    Required,
+
+    /// The key material must never leave the provider, in escrow or otherwise.
+    Forbidden,
+
+    /// The key material may be escrowed, but need not be.
+    Allowed,
 }
 
-impl SchemaBuilder {
-    pub fn new() -> Self {
-        Self {
-            runes: DEFAULT_RUNES.iter().map(|rune| (rune.variant_index(), rune.clone())).collect(),
+/// A use to which a [`crate::provider::BindRune`] may be put.  See [`Rune::KeyUsage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum KeyUsage {
+    Sign,
+    Verify,
+    /// Sealing data for confidentiality, with [`crate::provider::BindRune::seal`].
+    Encrypt,
+    /// Unsealing data, with [`crate::provider::BindRune::unseal`].
+    Decrypt,
+    /// Wrapping other keys, e.g. with [`crate::provider::BindRune::export_wrapped`].
+    Wrap,
+    /// Deriving other keys.
+    Derive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OriginIdentity;
+
+/// A period of time, such as the validity period of a key reported by [`Rune::CryptoPeriod`].
+/// The period includes `begin` but not `end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CryptoPeriod {
+    #[cfg_attr(feature = "serde", serde(with = "zoned_serde"))]
+    pub begin: Zoned,
+    #[cfg_attr(feature = "serde", serde(with = "zoned_serde"))]
+    pub end: Zoned,
+}
+
+impl CryptoPeriod {
+    /// Creates a period, failing with [`Error::InvalidCryptoPeriod`] unless `begin` is before
+    /// `end`.
+    pub fn new(begin: Zoned, end: Zoned) -> Result<Self> {
+        if begin >= end {
+            return Err(Error::InvalidCryptoPeriod(format!(
+                "Begin {} must be before end {}",
+                begin, end
+            )));
         }
+        Ok(Self { begin, end })
     }
 
-    pub fn build(self) -> Schema {
-        Schema { runes: self.runes.into_values().collect() }
+    /// Returns true if `time` is within the period.
+    pub fn contains(&self, time: &Zoned) -> bool {
+        self.begin <= *time && *time < self.end
     }
 
-    /// Requires that the [`crate::provider::BindRune`] uses a public/private key pair, allowing the
-    /// public key to be distributed to other parties for encryption or verification.  If this
-    /// [`Rune`] is not provided, the `BindRune` uses a symmetric key or a shared secret.
-    pub fn public_private_key_pair(mut self) -> Self {
-        let rune = Rune::PublicPrivateKeyPair;
-        self.runes.insert(rune.variant_index(), rune);
-        self
+    /// Returns true if `other` lies entirely within this period.
+    pub fn encloses(&self, other: &CryptoPeriod) -> bool {
+        self.begin <= other.begin && other.end <= self.end
     }
 
-    /// Requires that the [`crate::provider::BindRune`] provides confidentiality for the specified
-    /// amount of time, from the moment of key generation.  Confidentiality years are estimates,
-    /// based on NIST SP 800-57 Part 1, table 2 or similar sources.  They are best-estimate
-    /// projections based on the current state of the art.
-    ///
-    /// Callers should endeavor to specify the shortest period of time that they can tolerate.  At
-    /// present, the confidentiality period is only used to select the appropriate construction, but
-    /// in the future it may be used in other ways, for example to identify keys that need rotation.
-    pub fn confidentiality<P: PlatformAbstractions>(mut self, duration: Span) -> Result<Self> {
-        let end_time =
-            P::get_current_time().datetime().checked_add(duration).map_err(Error::from)?;
-        let rune = Rune::Confidentiality { end_time };
-        self.runes.insert(rune.variant_index(), rune);
-        Ok(self)
+    /// Returns true if the periods have any time in common.
+    pub fn overlaps(&self, other: &CryptoPeriod) -> bool {
+        self.begin < other.end && other.begin < self.end
     }
 
-    pub fn security_bits(mut self, security_bits: u8) -> Self {
-        let rune = Rune::SecurityBits(security_bits);
-        self.runes.insert(rune.variant_index(), rune);
-        self
+    /// Returns the time the periods have in common, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &CryptoPeriod) -> Option<CryptoPeriod> {
+        let begin = (&self.begin).max(&other.begin).clone();
+        let end = (&self.end).min(&other.end).clone();
+        CryptoPeriod::new(begin, end).ok()
     }
+}
 
-    pub fn message_limit(mut self, message_limit: u128) -> Result<Self> {
-        if message_limit == u128::MAX {
-            return Err(Error::InvalidMessageLimit(format!("Message limit cannot be unbounded")));
-        }
-        let rune = Rune::MessageLimit(message_limit);
-        self.runes.insert(rune.variant_index(), rune);
-        Ok(self)
-    }
+/// A third-party security certification, such as FIPS 140-3 Level 2.  See
+/// [`Rune::Certifications`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SecurityCertification {
+    pub scheme: CertificationScheme,
+    pub level: CertificationLevel,
+}
 
-    pub fn enforced_message_limit(mut self, enforced_message_limit: u128) -> Result<Self> {
-        if enforced_message_limit == u128::MAX {
-            return Err(Error::InvalidMessageLimit(format!("Message limit cannot be unbounded")));
-        }
-        let rune = Rune::EnforcedMessageLimit(enforced_message_limit);
-        self.runes.insert(rune.variant_index(), rune);
-        Ok(self)
+impl SecurityCertification {
+    /// Returns true if this certification is at least as strong as `required`.
+    fn meets(&self, required: &SecurityCertification) -> bool {
+        self.scheme == required.scheme && self.level >= required.level
     }
+}
 
-    pub fn message_size_limit(mut self, message_size_limit: u128) -> Result<Self> {
-        if message_size_limit == u128::MAX {
-            return Err(Error::InvalidMessageSizeLimit(format!(
-                "Message size limit cannot be unbounded"
-            )));
-        }
+/// The scheme under which a [`SecurityCertification`] was issued.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CertificationScheme {
+    /// NIST FIPS 140-3, with security levels 1 to 4.
+    Fips140_3,
+    /// Common Criteria, with evaluation assurance levels (EAL) 1 to 7.
+    CommonCriteria,
+}
+
+/// The level of a [`SecurityCertification`], from weakest to strongest.  The meaning of each level
+/// depends on the [`CertificationScheme`]; for Common Criteria, `Level4` is EAL4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CertificationLevel {
+    Level1,
+    Level2,
+    Level3,
+    Level4,
+    Level5,
+    Level6,
+    Level7,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "SerializedSchema"))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Schema {
+    runes: Vec<Rune>,
+    /// Soft requirements, used only to rank constructions that satisfy `runes`.  See
+    /// [`SchemaBuilder::prefer`].
+    preferences: Vec<Rune>,
+    /// Constructions that must not be selected, sorted.  See
+    /// [`SchemaBuilder::deny_construction`].
+    denied: Vec<ConstructionIdentifier>,
+}
+
+/// Serialization of [`Zoned`] times in jiff's usual format, such as
+/// `2030-01-01T00:00:00+00:00[UTC]`.  Deserialization resolves time zone names with the time zone
+/// database bundled into jiff, since `no_std` builds have no global database to consult.
+#[cfg(feature = "serde")]
+mod zoned_serde {
+    use alloc::string::String;
+    use jiff::{Zoned, fmt::temporal::DateTimeParser, tz::TimeZoneDatabase};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    pub(super) fn serialize<S: Serializer>(
+        zoned: &Zoned,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        zoned.serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Zoned, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        DateTimeParser::new()
+            .parse_zoned_with(&TimeZoneDatabase::bundled(), &text)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// The serialized form of a [`Schema`], which is canonicalized on deserialization so that
+/// schemas from other sources, which may be out of order or contain duplicates, uphold the same
+/// invariants as those built with [`SchemaBuilder`].  Of duplicate runes, the last wins.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedSchema {
+    runes: Vec<Rune>,
+    preferences: Vec<Rune>,
+    #[serde(default)]
+    denied: Vec<ConstructionIdentifier>,
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedSchema> for Schema {
+    fn from(serialized: SerializedSchema) -> Self {
+        let canonical = |runes: Vec<Rune>| {
+            let runes: BTreeMap<u32, Rune> =
+                runes.into_iter().map(|rune| (rune.variant_index(), rune)).collect();
+            runes.into_values().map(Rune::canonical).collect()
+        };
+        let mut denied = serialized.denied;
+        canonicalize(&mut denied);
+        Schema {
+            runes: canonical(serialized.runes),
+            preferences: canonical(serialized.preferences),
+            denied,
+        }
+    }
+}
+
+impl Schema {
+    /// Returns a schema that, on every axis, is at least as strong as each of `schemas`.  Runes
+    /// present in any of the schemas are present in the result.
+    pub(crate) fn upper_bound(schemas: impl IntoIterator<Item = Schema>) -> Schema {
+        let mut runes: BTreeMap<u32, Rune> = BTreeMap::new();
+        for rune in schemas.into_iter().flat_map(|schema| schema.runes) {
+            let index = rune.variant_index();
+            let rune = match runes.get(&index) {
+                Some(existing) => existing.stronger(&rune),
+                None => rune,
+            };
+            runes.insert(index, rune);
+        }
+        Schema {
+            runes: runes.into_values().map(Rune::canonical).collect(),
+            preferences: Vec::new(),
+            denied: Vec::new(),
+        }
+    }
+
+    /// Returns true if the schema is in canonical form: runes are sorted by variant index, with at
+    /// most one rune per index, and vector payloads are sorted and free of duplicates.  Every
+    /// schema produced by this crate is canonical, which makes equality comparison and
+    /// serialization of equivalent schemas deterministic.
+    pub fn is_canonical(&self) -> bool {
+        [&self.runes, &self.preferences].into_iter().all(|runes| {
+            runes.windows(2).all(|pair| pair[0].variant_index() < pair[1].variant_index())
+                && runes.iter().all(Rune::is_canonical)
+        }) && is_canonical(&self.denied)
+    }
+
+    /// Returns the runes of the schema, sorted by variant index, for inspecting the capabilities
+    /// reported by a construction or [`crate::provider::BindRune`].
+    pub fn runes(&self) -> &[Rune] {
+        &self.runes
+    }
+
+    /// Returns the soft requirements of the schema.  See [`SchemaBuilder::prefer`].
+    pub fn preferences(&self) -> &[Rune] {
+        &self.preferences
+    }
+
+    /// Returns the constructions that must not be selected, sorted by identifier.  See
+    /// [`SchemaBuilder::deny_construction`].
+    pub fn denied_constructions(&self) -> &[ConstructionIdentifier] {
+        &self.denied
+    }
+
+    /// Returns true if `construction` must not be selected to satisfy this schema.
+    pub fn denies(&self, construction: &ConstructionIdentifier) -> bool {
+        self.denied.binary_search(construction).is_ok()
+    }
+
+    /// Returns the number of `requirements`' preferences that this schema does not meet.
+    pub(crate) fn unmet_preferences(
+        &self,
+        requirements: &Schema,
+        comparators: &RuneComparators,
+    ) -> usize {
+        requirements
+            .preferences
+            .iter()
+            .filter(|preference| {
+                comparators.coverage(preference, self.get(preference.reported_index()))
+                    == Coverage::Unmet
+            })
+            .count()
+    }
+
+    /// Returns the lowest level at which a certification under `scheme` would satisfy this
+    /// schema's [`Rune::Certifications`], or `None` if the schema doesn't list `scheme`.  Because
+    /// only one of the listed certifications need be provided, `Some` means the scheme is
+    /// acceptable, not that it is mandatory, unless it is the only scheme listed.
+    pub fn requires_certification(
+        &self,
+        scheme: CertificationScheme,
+    ) -> Option<CertificationLevel> {
+        match self.get(Rune::Certifications(Vec::new()).variant_index()) {
+            Some(Rune::Certifications(certifications)) => certifications
+                .iter()
+                .filter(|certification| certification.scheme == scheme)
+                .map(|certification| certification.level)
+                .min(),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`Rune::DerivationDepth`] to stamp on a subkey derived from a
+    /// [`crate::provider::BindRune`] with this schema, failing with [`Error::DerivationTooDeep`]
+    /// if it exceeds the maximum depth in `requirements`.  A `BindRune` that doesn't report a
+    /// depth is treated as a root.  Providers must call this from
+    /// [`crate::provider::BindRune::derive_subkey`].
+    pub fn child_derivation_depth(&self, requirements: &Schema) -> Result<u8> {
+        let index = Rune::DerivationDepth(0).variant_index();
+        let depth = match self.get(index) {
+            Some(Rune::DerivationDepth(depth)) => depth.saturating_add(1),
+            _ => 1,
+        };
+        match requirements.get(index) {
+            Some(Rune::DerivationDepth(max)) if depth > *max => Err(Error::DerivationTooDeep(
+                format!("Subkey depth {} exceeds maximum {}", depth, max),
+            )),
+            _ => Ok(depth),
+        }
+    }
+
+    /// Returns true if the [`crate::provider::BindRune`] uses a public/private key pair.  See
+    /// [`Rune::PublicPrivateKeyPair`].
+    pub fn is_asymmetric(&self) -> bool {
+        self.get(Rune::PublicPrivateKeyPair.variant_index()).is_some()
+    }
+
+    /// Returns true if the [`crate::provider::BindRune`] uses only a symmetric key or shared
+    /// secret.  Operations that only make sense on key pairs, such as
+    /// [`crate::provider::BindRune::public_key`], fail on such `BindRune`s with
+    /// [`Error::KeyUsageViolation`].
+    pub fn is_symmetric_only(&self) -> bool {
+        !self.is_asymmetric()
+    }
+
+    /// Returns the period during which the [`crate::provider::BindRune`] may be used, if limited.
+    /// See [`Rune::CryptoPeriod`].
+    pub fn crypto_period(&self) -> Option<CryptoPeriod> {
+        self.runes.iter().find_map(|rune| match rune {
+            Rune::CryptoPeriod { begin, end } => {
+                Some(CryptoPeriod { begin: begin.clone(), end: end.clone() })
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns the escrow policy of the [`crate::provider::BindRune`].  If the schema does not
+    /// contain [`Rune::Escrow`], escrow is [`EscrowPolicy::Allowed`].
+    pub fn escrow_policy(&self) -> EscrowPolicy {
+        match self.get(Rune::Escrow(EscrowPolicy::Allowed).variant_index()) {
+            Some(Rune::Escrow(policy)) => *policy,
+            _ => EscrowPolicy::Allowed,
+        }
+    }
+
+    /// Returns the domain the [`crate::provider::BindRune`] is bound to, if any.  Providers must
+    /// mix this into the variation and associated data of every operation.  See
+    /// [`Rune::DomainSeparation`].
+    pub fn domain(&self) -> Option<&str> {
+        match self.get(Rune::DomainSeparation(String::new()).variant_index()) {
+            Some(Rune::DomainSeparation(domain)) => Some(domain),
+            _ => None,
+        }
+    }
+
+    /// Returns the largest single message, in bytes, that the [`crate::provider::BindRune`]
+    /// accepts, from [`Rune::EnforcedMessageSizeLimit`] or [`Rune::MessageSizeLimit`].  Callers
+    /// can check input sizes against it before sealing, rather than having the operation fail
+    /// part way through with [`Error::MessageTooLong`].  Returns `None` if message size is
+    /// unbounded, either because the schema has no size limit or because it reports a limit of
+    /// `u128::MAX`, which is how [`crate::provider::CounterState`] represents an unenforced limit.
+    pub fn max_message_bytes(&self) -> Option<u128> {
+        self.get(Rune::MessageSizeLimit(0).variant_index())
+            .and_then(Rune::limit)
+            .filter(|limit| *limit != u128::MAX)
+    }
+
+    /// Returns an error unless the [`crate::provider::BindRune`] may be used for `usage`.
+    /// Providers must call this before performing any operation.  See [`Rune::KeyUsage`].
+    pub fn check_usage(&self, usage: KeyUsage) -> Result<()> {
+        match self.get(Rune::KeyUsage(Vec::new()).variant_index()) {
+            Some(Rune::KeyUsage(usages)) if !usages.contains(&usage) => {
+                Err(Error::KeyUsageViolation(format!("{:?} is not among {:?}", usage, usages)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the value of the `Enforced*` limit rune of the same variant as `enforced`, if
+    /// present.  Requested limits at the same variant index are ignored.
+    pub(crate) fn enforced_limit(&self, enforced: &Rune) -> Option<u128> {
+        match self.get(enforced.variant_index()) {
+            Some(rune) if mem::discriminant(rune) == mem::discriminant(enforced) => rune.limit(),
+            _ => None,
+        }
+    }
+
+    /// Returns the rune at the specified variant index, if present, e.g. the schema's security
+    /// bits with `schema.get(Rune::SecurityBits(0).variant_index())`.
+    pub fn get(&self, variant_index: u32) -> Option<&Rune> {
+        let position = self.runes.binary_search_by_key(&variant_index, Rune::variant_index).ok()?;
+        Some(&self.runes[position])
+    }
+
+    /// Returns a ranking key describing how much this schema exceeds `requirements`: the number of
+    /// runes that exceed a requirement or that weren't requested at all, followed by the reported
+    /// security bits.  Lower keys indicate less over-provisioning.
+    pub(crate) fn margin_over(
+        &self,
+        requirements: &Schema,
+        comparators: &RuneComparators,
+    ) -> (usize, u8) {
+        let excess = self
+            .runes
+            .iter()
+            .filter(|rune| match requirements.get(rune.variant_index()) {
+                Some(requirement) => {
+                    comparators.coverage(requirement, Some(rune)) == Coverage::Exceeded
+                }
+                None => true,
+            })
+            .count();
+        let security_bits = match self.get(Rune::SecurityBits(0).variant_index()) {
+            Some(Rune::SecurityBits(bits)) => *bits,
+            _ => 0,
+        };
+        (excess, security_bits)
+    }
+
+    /// Reports, for each [`Rune`] in `baseline`, whether this schema leaves it unmet, meets it
+    /// exactly, or exceeds it.  This is intended for compliance checks of a forged
+    /// [`crate::provider::BindRune`] or a construction against an organizational baseline policy.
+    ///
+    /// Runes present in this schema but absent from `baseline` are not reported.
+    pub fn coverage(&self, baseline: &Schema) -> CoverageReport {
+        self.coverage_with(baseline, &RuneComparators::new())
+    }
+
+    /// Like [`Schema::coverage`], but assesses [`Rune::Extension`]s with the rules registered in
+    /// `comparators`, where one is registered for the extension's `id`.
+    pub fn coverage_with(
+        &self,
+        baseline: &Schema,
+        comparators: &RuneComparators,
+    ) -> CoverageReport {
+        CoverageReport {
+            entries: baseline
+                .runes
+                .iter()
+                .map(|requirement| CoverageEntry {
+                    requirement: requirement.clone(),
+                    coverage: comparators
+                        .coverage(requirement, self.get(requirement.reported_index())),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns true if this schema, as reported by a construction or
+    /// [`crate::provider::BindRune`], meets every rune of `requirements`.  Each requirement is
+    /// compared with the rune this schema reports on the same axis, and is met if that rune is at
+    /// least as strong: for example, `SecurityBits(n)` is met by `SecurityBits(m)` with `m >= n`,
+    /// limits are met by requested or enforced limits at least as high, vector runes such as
+    /// [`Rune::SoftwareSideChannelResistance`] are met by a superset, and unit runes such as
+    /// [`Rune::QuantumResistance`] must be present.  Requirements absent from this schema are
+    /// unmet.
+    ///
+    /// Use [`Schema::coverage`] to find out which requirements are unmet.
+    pub fn satisfies(&self, requirements: &Schema) -> bool {
+        self.satisfies_with(requirements, &RuneComparators::new())
+    }
+
+    /// Like [`Schema::satisfies`], but assesses [`Rune::Extension`]s with the rules registered in
+    /// `comparators`.
+    pub fn satisfies_with(&self, requirements: &Schema, comparators: &RuneComparators) -> bool {
+        requirements.runes.iter().all(|requirement| {
+            comparators.coverage(requirement, self.get(requirement.reported_index()))
+                != Coverage::Unmet
+        })
+    }
+
+    /// Returns (required, provided) pairs for the runes of `requirement` that this schema
+    /// substantially exceeds on axes where strength has a performance or monetary cost, to flag
+    /// keys that could be right-sized.  These axes are:
+    ///
+    /// - [`Rune::SecurityBits`], if exceeded by 64 bits or more, e.g. 256 bits for a 128-bit need.
+    /// - [`Rune::Isolated`], if the isolation level is higher than required.
+    /// - [`Rune::SoftwareSideChannelResistance`] and [`Rune::HardwareSideChannelResistance`], if
+    ///   resistances beyond those required are provided.
+    ///
+    /// Other runes, such as limits and time horizons, are routinely exceeded at no cost and are
+    /// not reported.
+    pub fn over_provisioning(&self, requirement: &Schema) -> Vec<(Rune, Rune)> {
+        requirement
+            .runes
+            .iter()
+            .filter_map(|required| {
+                let provided = self.get(required.variant_index())?;
+                let substantial = match (required, provided) {
+                    (Rune::SecurityBits(required), Rune::SecurityBits(provided)) => {
+                        provided.saturating_sub(*required) >= 64
+                    }
+                    (Rune::Isolated(_), Rune::Isolated(_))
+                    | (
+                        Rune::SoftwareSideChannelResistance(_),
+                        Rune::SoftwareSideChannelResistance(_),
+                    )
+                    | (
+                        Rune::HardwareSideChannelResistance(_),
+                        Rune::HardwareSideChannelResistance(_),
+                    ) => required.coverage_by(Some(provided)) == Coverage::Exceeded,
+                    _ => false,
+                };
+                substantial.then(|| (required.clone(), provided.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns a schema combining the requirements of this schema and `other`, such that a
+    /// capability satisfying the result satisfies both wherever that can be expressed.  Runes
+    /// present in only one schema are copied; for runes present in both, the stricter wins:
+    ///
+    /// - Minimums, such as [`Rune::SecurityBits`], [`Rune::MinTagBits`], [`Rune::Isolated`] and
+    ///   the confidentiality, integrity and authentication horizons, take the higher value.
+    /// - Requested limits take the higher value, since a construction must support both, but an
+    ///   `Enforced*` limit in either schema takes precedence over a requested one, and of two
+    ///   enforced limits the lower value wins.
+    /// - [`Rune::CryptoPeriod`] spans both periods.
+    /// - Side channel resistances, which must all be provided, take the union.
+    /// - [`Rune::KeyUsage`], which lists the usages permitted, and [`Rune::Certifications`], of
+    ///   which only one need be provided, take the intersection.
+    /// - [`EscrowPolicy::Allowed`] yields to a specific policy, and
+    ///   [`VariationStrategy::Automatic`] wins over a caller-provided variation.  Caller-provided
+    ///   variations of different types merge to [`VariationType::Arbitrary`].
+    ///
+    /// Fails with [`Error::ConflictingRequirements`] where the requirements conflict
+    /// irreconcilably: different domains, key origins, attestation formats, authentication
+    /// origins or escrow policies, or key usages or certification lists with nothing in common.
+    /// Preferences are merged by the same rules, except that conflicting preferences keep this
+    /// schema's value, since preferences never cause a request to be rejected.  Denied
+    /// constructions are combined, so that a construction denied by either schema is denied.
+    pub fn merge(&self, other: &Schema) -> Result<Schema> {
+        let merge = |a: &[Rune], b: &[Rune], lenient: bool| -> Result<Vec<Rune>> {
+            let mut runes: BTreeMap<u32, Rune> =
+                a.iter().map(|rune| (rune.variant_index(), rune.clone())).collect();
+            for rune in b {
+                let index = rune.variant_index();
+                let rune = match runes.get(&index) {
+                    Some(existing) if lenient => {
+                        existing.stricter(rune).unwrap_or_else(|_| existing.clone())
+                    }
+                    Some(existing) => existing.stricter(rune)?,
+                    None => rune.clone(),
+                };
+                runes.insert(index, rune);
+            }
+            Ok(runes.into_values().map(Rune::canonical).collect())
+        };
+        Ok(Schema {
+            runes: merge(&self.runes, &other.runes, false)?,
+            preferences: merge(&self.preferences, &other.preferences, true)?,
+            denied: {
+                let mut denied = union(&self.denied, &other.denied);
+                canonicalize(&mut denied);
+                denied
+            },
+        })
+    }
+
+    /// Returns a compact, collision-resistant identifier for the schema, for cross-referencing
+    /// policies in logs without recording them in full.  The fingerprint is the SHA-256 hash of
+    /// the schema's CBOR encoding.  Since schemas are canonical, equivalent schemas have the same
+    /// fingerprint, however they were built, and any semantic difference changes it.
+    ///
+    /// Fingerprints are only stable across crate versions to the extent that the serialized form
+    /// is; see the [module documentation](self).  Only available with the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn fingerprint(&self) -> [u8; 32] {
+        use serde::Serialize;
+        use sha2::Digest;
+
+        let mut serializer = cbor4ii::serde::Serializer::new(HashWriter(sha2::Sha256::new()));
+        self.serialize(&mut serializer).expect("Schemas always serialize to CBOR");
+        serializer.into_inner().0.finalize().into()
+    }
+
+    /// Returns the runes of `stricter` that this schema does not meet, i.e. the incremental
+    /// requirements a key with this schema would have to gain to comply with `stricter`.  For
+    /// example, the delta from a 128-bit classical schema to a 192-bit quantum-resistant policy
+    /// contains `SecurityBits(192)` and `QuantumResistance`.
+    ///
+    /// Runes are copied from `stricter` unchanged, so the result can be used directly as a
+    /// requirement.  An empty delta means this schema already satisfies `stricter`.
+    pub fn delta(&self, stricter: &Schema) -> Schema {
+        self.delta_with(stricter, &RuneComparators::new())
+    }
+
+    /// Like [`Schema::delta`], but assesses [`Rune::Extension`]s with the rules registered in
+    /// `comparators`.
+    pub fn delta_with(&self, stricter: &Schema, comparators: &RuneComparators) -> Schema {
+        Schema {
+            runes: self.coverage_with(stricter, comparators).unmet().cloned().collect(),
+            preferences: Vec::new(),
+            denied: Vec::new(),
+        }
+    }
+
+    /// Compares the runes of this schema with those of `other`, axis by axis, e.g. to show how
+    /// much the schema reported by a forged [`crate::provider::BindRune`] exceeds the requested
+    /// one.  Axes on which the schemas have identical runes are omitted, so the diff of equal
+    /// schemas is empty.  Preferences and denied constructions are not compared.
+    pub fn diff(&self, other: &Schema) -> SchemaDiff {
+        let mut entries = Vec::new();
+        let (mut ours, mut theirs) = (self.runes.iter().peekable(), other.runes.iter().peekable());
+        loop {
+            let (this, other) = match (ours.peek(), theirs.peek()) {
+                (None, None) => break,
+                (Some(a), Some(b)) if a.variant_index() == b.variant_index() => {
+                    (ours.next(), theirs.next())
+                }
+                (Some(a), Some(b)) if a.variant_index() > b.variant_index() => {
+                    (None, theirs.next())
+                }
+                (Some(_), _) => (ours.next(), None),
+                (None, Some(_)) => (None, theirs.next()),
+            };
+            if this == other {
+                continue;
+            }
+            entries.push(RuneDifference {
+                this: this.cloned(),
+                other: other.cloned(),
+                delta: this
+                    .and_then(Rune::magnitude)
+                    .zip(other.and_then(Rune::magnitude))
+                    .map(|(a, b)| signed_delta(a, b)),
+            });
+        }
+        SchemaDiff { entries }
+    }
+}
+
+/// Renders the rune for human readers, such as support engineers reading logs, e.g.
+/// `security_bits: 128` or `quantum_resistance`.  Limits of 2¹²⁸ render as `unbounded`.  The
+/// rendering is not a stable format; use serde to record runes for machines.
+impl fmt::Display for Rune {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rune::PublicPrivateKeyPair => write!(f, "public_private_key_pair"),
+            Rune::SecurityBits(bits) => write!(f, "security_bits: {}", bits),
+            Rune::MessageLimit(value) => write_limit(f, "message_limit", *value),
+            Rune::EnforcedMessageLimit(value) => write_limit(f, "enforced_message_limit", *value),
+            Rune::MessageSizeLimit(value) => write_limit(f, "message_size_limit", *value),
+            Rune::EnforcedMessageSizeLimit(value) => {
+                write_limit(f, "enforced_message_size_limit", *value)
+            }
+            Rune::TotalDataLimit(value) => write_limit(f, "total_data_limit", *value),
+            Rune::EnforcedTotalDataLimit(value) => {
+                write_limit(f, "enforced_total_data_limit", *value)
+            }
+            Rune::Confidentiality { end_time } => write!(f, "confidentiality: until {}", end_time),
+            Rune::Integrity { year } => write!(f, "integrity: through {}", year),
+            Rune::Authentication { year, .. } => write!(f, "authentication: through {}", year),
+            Rune::CryptoPeriod { begin, end } => write!(f, "crypto_period: {} .. {}", begin, end),
+            Rune::QuantumResistance => write!(f, "quantum_resistance"),
+            Rune::SoftwareSideChannelResistance(resistances) => {
+                write_list(f, "software_side_channel_resistance", resistances)
+            }
+            Rune::HardwareSideChannelResistance(resistances) => {
+                write_list(f, "hardware_side_channel_resistance", resistances)
+            }
+            Rune::Isolated(level) => write!(f, "isolated: {:?}", level),
+            Rune::Certifications(certifications) => {
+                write!(f, "certifications:")?;
+                for (i, certification) in certifications.iter().enumerate() {
+                    write!(
+                        f,
+                        "{} {:?} {:?}",
+                        if i == 0 { "" } else { "," },
+                        certification.scheme,
+                        certification.level
+                    )?;
+                }
+                Ok(())
+            }
+            Rune::VariationStrategy(VariationStrategy::Automatic) => {
+                write!(f, "variation_strategy: Automatic")
+            }
+            Rune::VariationStrategy(VariationStrategy::CallerProvided(variation_type)) => {
+                write!(f, "variation_strategy: CallerProvided {:?}", variation_type)
+            }
+            Rune::AuditLogged => write!(f, "audit_logged"),
+            Rune::DomainSeparation(domain) => write!(f, "domain_separation: {:?}", domain),
+            Rune::KeyOrigin(origin) => write!(f, "key_origin: {:?}", origin),
+            Rune::Escrow(policy) => write!(f, "escrow: {:?}", policy),
+            Rune::MinTagBits(bits) => write!(f, "min_tag_bits: {}", bits),
+            Rune::KeyUsage(usages) => write_list(f, "key_usage", usages),
+            Rune::AntiRollback => write!(f, "anti_rollback"),
+            Rune::OpenImplementation => write!(f, "open_implementation"),
+            Rune::KeyCommitment => write!(f, "key_commitment"),
+            Rune::DerivationDepth(depth) => write!(f, "derivation_depth: {}", depth),
+            Rune::UsageProof => write!(f, "usage_proof"),
+            Rune::MaxEnforcedMessageLimit(value) => {
+                write_limit(f, "max_enforced_message_limit", *value)
+            }
+            Rune::ForwardSecrecy => write!(f, "forward_secrecy"),
+            Rune::NonceMisuseResistance => write!(f, "nonce_misuse_resistance"),
+            Rune::Attestation(format) => write!(f, "attestation: {:?}", format),
+            Rune::KeyRotationInterval(interval) => {
+                write!(f, "key_rotation_interval: {:#}", interval)
+            }
+            Rune::EntropySource(source) => write!(f, "entropy_source: {:?}", source),
+            Rune::Deterministic => write!(f, "deterministic"),
+            Rune::MemoryHardness(params) => write!(
+                f,
+                "memory_hardness: {} KiB, {} iterations, parallelism {}",
+                params.memory_kib, params.iterations, params.parallelism
+            ),
+            Rune::ContributoryKeyAgreement => write!(f, "contributory_key_agreement"),
+            Rune::MultiRecipient { max } => write!(f, "multi_recipient: up to {}", max),
+            Rune::Threshold { t, n } => write!(f, "threshold: {} of {}", t, n),
+            Rune::Extension { id, value } => {
+                write!(f, "extension {}: {}", id, hex_encode(value))
+            }
+        }
+    }
+}
+
+/// Renders the schema for human readers, one rune per line in variant index order, followed by
+/// the preferences, each prefixed with `prefer`, and the denied constructions.  See the
+/// [`fmt::Display`] implementation of [`Rune`].
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        for rune in &self.runes {
+            write!(f, "{}{}", separator, rune)?;
+            separator = "\n";
+        }
+        for rune in &self.preferences {
+            write!(f, "{}prefer {}", separator, rune)?;
+            separator = "\n";
+        }
+        for construction in &self.denied {
+            write!(f, "{}deny: {}", separator, construction.as_str())?;
+            separator = "\n";
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Schema {
+    type Item = &'a Rune;
+    type IntoIter = core::slice::Iter<'a, Rune>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.runes.iter()
+    }
+}
+
+/// The degree to which a reported [`Rune`] meets a required one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Coverage {
+    /// The requirement is not met, either because the corresponding rune is absent or because it
+    /// is weaker than required.
+    Unmet,
+
+    /// The requirement is met exactly.
+    Met,
+
+    /// The requirement is met and the reported rune is stronger than required, for example 256
+    /// security bits when 128 were required.
+    Exceeded,
+}
+
+impl Coverage {
+    fn compare<T: Ord + ?Sized>(provided: &T, required: &T) -> Self {
+        match provided.cmp(required) {
+            core::cmp::Ordering::Less => Coverage::Unmet,
+            core::cmp::Ordering::Equal => Coverage::Met,
+            core::cmp::Ordering::Greater => Coverage::Exceeded,
+        }
+    }
+
+    fn exact<T: PartialEq + ?Sized>(provided: &T, required: &T) -> Self {
+        if provided == required { Coverage::Met } else { Coverage::Unmet }
+    }
+
+    fn superset<T: PartialEq>(provided: &[T], required: &[T]) -> Self {
+        if !required.iter().all(|item| provided.contains(item)) {
+            Coverage::Unmet
+        } else if provided.iter().all(|item| required.contains(item)) {
+            Coverage::Met
+        } else {
+            Coverage::Exceeded
+        }
+    }
+}
+
+/// A rule for assessing how well a provided [`Rune::Extension`] meets a required one with the
+/// same `id`, given their values.  See [`RuneComparators`].
+pub trait RuneComparator {
+    fn coverage(&self, required: &[u8], provided: &[u8]) -> Coverage;
+}
+
+/// The comparison rules for [`Rune::Extension`]s, keyed by extension `id`, for crates that define
+/// their own runes.  Extensions without a registered rule must match exactly.
+#[derive(Default)]
+pub struct RuneComparators {
+    comparators: BTreeMap<u16, Box<dyn RuneComparator>>,
+}
+
+impl RuneComparators {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `comparator` for extensions with the specified `id`, replacing any rule
+    /// registered earlier.
+    pub fn register(&mut self, id: u16, comparator: Box<dyn RuneComparator>) {
+        self.comparators.insert(id, comparator);
+    }
+
+    pub(crate) fn coverage(&self, required: &Rune, provided: Option<&Rune>) -> Coverage {
+        match (required, provided) {
+            (
+                Rune::Extension { id, value: required },
+                Some(Rune::Extension { value: provided, .. }),
+            ) => match self.comparators.get(id) {
+                Some(comparator) => comparator.coverage(required, provided),
+                None => Coverage::exact(provided, required),
+            },
+            _ => required.coverage_by(provided),
+        }
+    }
+}
+
+/// The assessment of a single baseline [`Rune`] in a [`CoverageReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageEntry {
+    pub requirement: Rune,
+    pub coverage: Coverage,
+}
+
+/// A per-requirement report of how a [`Schema`] measures up against a baseline, produced by
+/// [`Schema::coverage`].  Entries are in the same order as the baseline's runes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageReport {
+    entries: Vec<CoverageEntry>,
+}
+
+impl CoverageReport {
+    pub fn entries(&self) -> &[CoverageEntry] {
+        &self.entries
+    }
+
+    /// Returns true if no baseline requirement is unmet.
+    pub fn is_satisfied(&self) -> bool {
+        self.entries.iter().all(|entry| entry.coverage != Coverage::Unmet)
+    }
+
+    /// Returns the baseline requirements that are not met.
+    pub fn unmet(&self) -> impl Iterator<Item = &Rune> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.coverage == Coverage::Unmet)
+            .map(|entry| &entry.requirement)
+    }
+}
+
+/// How the runes of two schemas differ on a single axis, in a [`SchemaDiff`].  `this` is the rune
+/// of the schema on which [`Schema::diff`] was called and `other` that of the schema passed to
+/// it; at most one is `None`, if the rune is present in only one of the schemas.
+///
+/// When both runes are a single quantity, such as [`Rune::SecurityBits`] and the limits, `delta`
+/// is the value of `other` minus the value of `this`, saturating at the bounds of `i128`; e.g.
+/// 128 when 256 security bits were provided and 128 requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuneDifference {
+    pub this: Option<Rune>,
+    pub other: Option<Rune>,
+    pub delta: Option<i128>,
+}
+
+impl RuneDifference {
+    /// Returns the variant index of the axis on which the schemas differ.  See
+    /// [`Rune::variant_index`].
+    pub fn variant_index(&self) -> u32 {
+        self.this.as_ref().or(self.other.as_ref()).map_or(0, Rune::variant_index)
+    }
+}
+
+/// The per-axis differences between two schemas, produced by [`Schema::diff`].  Entries are in
+/// variant index order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchemaDiff {
+    entries: Vec<RuneDifference>,
+}
+
+impl SchemaDiff {
+    pub fn entries(&self) -> &[RuneDifference] {
+        &self.entries
+    }
+
+    /// Returns true if the schemas have identical runes.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the difference on the axis with the given variant index, if any.
+    pub fn get(&self, variant_index: u32) -> Option<&RuneDifference> {
+        self.entries.iter().find(|entry| entry.variant_index() == variant_index)
+    }
+}
+
+/// Returns `b - a`, saturating at the bounds of `i128`.
+fn signed_delta(a: u128, b: u128) -> i128 {
+    if b >= a {
+        i128::try_from(b - a).unwrap_or(i128::MAX)
+    } else {
+        i128::try_from(a - b).map_or(i128::MIN, |difference| -difference)
+    }
+}
+
+/// A CBOR output that hashes the encoding rather than storing it.  See [`Schema::fingerprint`].
+#[cfg(feature = "serde")]
+struct HashWriter(sha2::Sha256);
+
+#[cfg(feature = "serde")]
+impl cbor4ii::core::enc::Write for HashWriter {
+    type Error = core::convert::Infallible;
+
+    fn push(&mut self, input: &[u8]) -> core::result::Result<(), Self::Error> {
+        sha2::Digest::update(&mut self.0, input);
+        Ok(())
+    }
+}
+
+const DEFAULT_RUNES: [Rune; 4] = [
+    // The default message limit is 2¹⁶.
+    Rune::MessageLimit(2_u128.pow(16)),
+    // The default message size limit is 2¹⁶.
+    Rune::MessageSizeLimit(2_u128.pow(16)),
+    // The default total data limit is 2³².
+    Rune::TotalDataLimit(2_u128.pow(32)),
+    // By default, variations are generated automatically.
+    Rune::VariationStrategy(VariationStrategy::Automatic),
+];
+
+pub struct SchemaBuilder {
+    runes: BTreeMap<u32, Rune>,
+    preferences: BTreeMap<u32, Rune>,
+    denied: BTreeSet<ConstructionIdentifier>,
+}
+
+/// Helper macro to push an item to a vector-valued Rune variant, creating the variant if needed.
+/// Duplicates are filtered out.
+macro_rules! push_to_vec_rune {
+    ($runes:expr, $variant:ident, $item:expr) => {{
+        let item = $item;
+        let rune = Rune::$variant(vec![item]);
+        let index = rune.variant_index();
+        match $runes.insert(index, rune) {
+            Some(Rune::$variant(mut vec)) => {
+                if !vec.contains(&item) {
+                    vec.push(item);
+                }
+                $runes.insert(index, Rune::$variant(vec));
+            }
+            None => { /* Already inserted above */ }
+            _ => unreachable!("Got something other than the rune at its variant index"),
+        }
+    }};
+}
+
+impl Default for SchemaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchemaBuilder {
+    pub fn new() -> Self {
+        Self {
+            runes: DEFAULT_RUNES.iter().map(|rune| (rune.variant_index(), rune.clone())).collect(),
+            preferences: BTreeMap::new(),
+            denied: BTreeSet::new(),
+        }
+    }
+
+    /// Returns a builder preset for a signing key: a [`Rune::PublicPrivateKeyPair`] restricted to
+    /// [`KeyUsage::Sign`] and [`KeyUsage::Verify`], providing integrity and authentication of the
+    /// specified origin through the end of `year`, and no confidentiality.  Further requirements
+    /// can be added to the returned builder as usual.
+    pub fn for_signing(origin: OriginIdentity, year: u16) -> Self {
+        let mut builder = Self::new()
+            .public_private_key_pair()
+            .key_usage(KeyUsage::Sign)
+            .key_usage(KeyUsage::Verify);
+        for rune in [Rune::Integrity { year }, Rune::Authentication { origin, year }] {
+            builder.runes.insert(rune.variant_index(), rune);
+        }
+        builder
+    }
+
+    /// Returns a builder preset for an encryption key, restricted to [`KeyUsage::Encrypt`] and
+    /// [`KeyUsage::Decrypt`] and providing confidentiality through the end of `year`.  The key is
+    /// symmetric unless [`SchemaBuilder::public_private_key_pair`] is added.  For authenticated
+    /// encryption, add integrity and authentication with [`SchemaBuilder::aead_until`].
+    pub fn for_encryption(year: u16) -> Result<Self> {
+        let mut builder = Self::new().key_usage(KeyUsage::Encrypt).key_usage(KeyUsage::Decrypt);
+        let rune = Rune::Confidentiality { end_time: end_of_year(year)? };
+        builder.runes.insert(rune.variant_index(), rune);
+        Ok(builder)
+    }
+
+    /// Builds the [`Schema`], in canonical form (see [`Schema::is_canonical`]).
+    ///
+    /// The requirements are not checked for consistency, so the schema may be one that no
+    /// construction can satisfy.  Use [`SchemaBuilder::try_build`] to reject such schemas early.
+    pub fn build(self) -> Schema {
+        let schema = Schema {
+            runes: self.runes.into_values().map(Rune::canonical).collect(),
+            preferences: self.preferences.into_values().map(Rune::canonical).collect(),
+            denied: self.denied.into_iter().collect(),
+        };
+        debug_assert!(schema.is_canonical());
+        schema
+    }
+
+    /// Builds the [`Schema`], like [`SchemaBuilder::build`], but fails with
+    /// [`Error::ConflictingRequirements`] if the requirements contradict each other.  The rules
+    /// checked are:
+    ///
+    /// - [`Rune::Confidentiality`], [`Rune::Integrity`] and [`Rune::Authentication`] may not
+    ///   extend beyond the last year in which the requested [`Rune::SecurityBits`] is acceptable
+    ///   per NIST SP 800-57 Part 1, table 4: 2010 for fewer than 112 bits and 2030 for fewer than
+    ///   128 bits.
+    /// - The end of the [`Rune::CryptoPeriod`] may not be later than the
+    ///   [`Rune::Confidentiality`] end time, since data sealed at the end of the period must
+    ///   remain confidential for as long as requested.
+    /// - The [`Rune::MessageLimit`] or [`Rune::EnforcedMessageLimit`] may not exceed the
+    ///   [`Rune::MaxEnforcedMessageLimit`].
+    ///
+    /// Preferences are not checked, since they never cause a request to be rejected.
+    pub fn try_build(self) -> Result<Schema> {
+        let schema = self.build();
+        let conflict = |message: String| Err(Error::ConflictingRequirements(message));
+
+        let (mut security_bits, mut confidentiality_end, mut message_limit, mut max_message_limit) =
+            (None, None, None, None);
+        let mut horizons = Vec::new();
+        for rune in &schema {
+            match rune {
+                Rune::SecurityBits(bits) => security_bits = Some(*bits),
+                Rune::Confidentiality { end_time } => {
+                    confidentiality_end = Some(*end_time);
+                    horizons.push(("Confidentiality", end_time.year()));
+                }
+                Rune::Integrity { year } => horizons.push(("Integrity", saturating_year(*year))),
+                Rune::Authentication { year, .. } => {
+                    horizons.push(("Authentication", saturating_year(*year)))
+                }
+                Rune::MessageLimit(limit) | Rune::EnforcedMessageLimit(limit) => {
+                    message_limit = Some(*limit)
+                }
+                Rune::MaxEnforcedMessageLimit(max) => max_message_limit = Some(*max),
+                _ => {}
+            }
+        }
+
+        if let Some(bits) = security_bits
+            && let Some(last_year) = security_bits_last_year(bits)
+            && let Some((axis, year)) = horizons.iter().find(|(_, year)| *year > last_year)
+        {
+            return conflict(format!(
+                "{} through {} is not possible with {} security bits, which are only acceptable \
+                 through {}",
+                axis, year, bits, last_year
+            ));
+        }
+
+        if let (Some(period), Some(end_time)) = (schema.crypto_period(), confidentiality_end)
+            && period.end.datetime() > end_time
+        {
+            return conflict(format!(
+                "Crypto period ends at {}, after confidentiality ends at {}",
+                period.end, end_time
+            ));
+        }
+
+        if let (Some(limit), Some(max)) = (message_limit, max_message_limit)
+            && limit > max
+        {
+            return conflict(format!(
+                "Message limit {} exceeds maximum enforced message limit {}",
+                limit, max
+            ));
+        }
+
+        Ok(schema)
+    }
+
+    /// Folds the requirements, preferences and denied constructions of `other` into this
+    /// builder, e.g. so that a library can assemble base requirements and let its caller add
+    /// more.  Conflicts are resolved as by [`Schema::merge`], so that the result is at least as
+    /// strict as either builder:
+    ///
+    /// - Unit runes, such as [`Rune::QuantumResistance`], are required if either builder requires
+    ///   them.
+    /// - Minimums, such as [`Rune::SecurityBits`], take the higher value.  Requested limits are
+    ///   also minimums and take the higher value, but an `Enforced*` limit takes precedence over a
+    ///   requested one, and of two enforced limits, or two [`Rune::MaxEnforcedMessageLimit`]
+    ///   caps, the lower value wins.
+    /// - Side channel resistances take the union, while [`Rune::KeyUsage`] and
+    ///   [`Rune::Certifications`] take the intersection.
+    ///
+    /// Fails with [`Error::ConflictingRequirements`] if the builders require irreconcilable
+    /// runes, such as different domains or escrow policies.  Conflicting preferences keep this
+    /// builder's value.
+    pub fn merge(mut self, other: SchemaBuilder) -> Result<Self> {
+        let fold = |runes: &mut BTreeMap<u32, Rune>, others: BTreeMap<u32, Rune>, lenient: bool| {
+            for (index, rune) in others {
+                let rune = match runes.get(&index) {
+                    Some(existing) if lenient => {
+                        existing.stricter(&rune).unwrap_or_else(|_| existing.clone())
+                    }
+                    Some(existing) => existing.stricter(&rune)?,
+                    None => rune,
+                };
+                runes.insert(index, rune);
+            }
+            Ok::<_, Error>(())
+        };
+        fold(&mut self.runes, other.runes, false)?;
+        fold(&mut self.preferences, other.preferences, true)?;
+        self.denied.extend(other.denied);
+        Ok(self)
+    }
+
+    /// Records `rune` as a preference rather than a requirement.  Preferences are used only to
+    /// rank the constructions that satisfy the requirements: a construction that meets more
+    /// preferences is chosen over one that meets fewer, regardless of how much either exceeds
+    /// the requirements.  Preferences never cause a request to be rejected with
+    /// [`Error::UnsatisfiableRequirements`]; if no satisfying construction meets them, the best
+    /// construction that doesn't is chosen.
+    ///
+    /// A preference replaces any earlier preference for the same axis, but does not affect a
+    /// requirement for that axis.
+    pub fn prefer(mut self, rune: Rune) -> Self {
+        self.preferences.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Bans `construction`, so that it is never selected for the request even if it satisfies all
+    /// of the requirements, e.g. to exclude 3DES regardless of its security bits.  Denial takes
+    /// precedence over every other means of choosing a construction, including preferences.
+    pub fn deny_construction(mut self, construction: ConstructionIdentifier) -> Self {
+        self.denied.insert(construction);
+        self
+    }
+
+    /// Prefers, but does not require, quantum resistance.  See [`SchemaBuilder::prefer`].
+    pub fn prefer_quantum_resistance(self) -> Self {
+        self.prefer(Rune::QuantumResistance)
+    }
+
+    /// Prefers, but does not require, at least `security_bits` bits of security.  See
+    /// [`SchemaBuilder::prefer`].
+    pub fn prefer_security_bits(self, security_bits: u8) -> Self {
+        self.prefer(Rune::SecurityBits(security_bits))
+    }
+
+    /// Requires that the [`crate::provider::BindRune`] uses a public/private key pair, allowing the
+    /// public key to be distributed to other parties for encryption or verification.  If this
+    /// [`Rune`] is not provided, the `BindRune` uses a symmetric key or a shared secret.
+    pub fn public_private_key_pair(mut self) -> Self {
+        let rune = Rune::PublicPrivateKeyPair;
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires that the [`crate::provider::BindRune`] provides confidentiality for the specified
+    /// amount of time, from the moment of key generation.  Confidentiality years are estimates,
+    /// based on NIST SP 800-57 Part 1, table 2 or similar sources.  They are best-estimate
+    /// projections based on the current state of the art.
+    ///
+    /// Callers should endeavor to specify the shortest period of time that they can tolerate.  At
+    /// present, the confidentiality period is only used to select the appropriate construction, but
+    /// in the future it may be used in other ways, for example to identify keys that need rotation.
+    pub fn confidentiality<P: PlatformAbstractions>(mut self, duration: Span) -> Result<Self> {
+        let end_time =
+            P::get_current_time().datetime().checked_add(duration).map_err(Error::from)?;
+        let rune = Rune::Confidentiality { end_time };
+        self.runes.insert(rune.variant_index(), rune);
+        Ok(self)
+    }
+
+    /// Requires that the [`crate::provider::BindRune`] provides integrity through the end of
+    /// `year`.  See [`Rune::Integrity`].
+    pub fn integrity(mut self, year: u16) -> Self {
+        let rune = Rune::Integrity { year };
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires that the [`crate::provider::BindRune`] provides integrity for the specified
+    /// amount of time, from the moment of key generation, rounded up to the end of the year.
+    /// See [`SchemaBuilder::confidentiality`].
+    pub fn integrity_for<P: PlatformAbstractions>(self, duration: Span) -> Result<Self> {
+        let end_time =
+            P::get_current_time().datetime().checked_add(duration).map_err(Error::from)?;
+        let year = u16::try_from(end_time.year()).map_err(|_| {
+            Error::InvalidParameter(format!("Integrity cannot end in year {}", end_time.year()))
+        })?;
+        Ok(self.integrity(year))
+    }
+
+    /// Requires that the [`crate::provider::BindRune`] provides authentication of the specified
+    /// origin through the end of `year`.  See [`Rune::Authentication`].
+    pub fn authentication(mut self, origin: OriginIdentity, year: u16) -> Self {
+        let rune = Rune::Authentication { origin, year };
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires confidentiality, integrity and authentication of the specified origin, all
+    /// through the end of `year`.  This is the usual requirement for authenticated encryption
+    /// (AEAD) and sets [`Rune::Confidentiality`], [`Rune::Integrity`] and [`Rune::Authentication`]
+    /// to the same horizon.
+    pub fn aead_until(mut self, origin: OriginIdentity, year: u16) -> Result<Self> {
+        let end_time = end_of_year(year)?;
+        for rune in [
+            Rune::Confidentiality { end_time },
+            Rune::Integrity { year },
+            Rune::Authentication { origin, year },
+        ] {
+            self.runes.insert(rune.variant_index(), rune);
+        }
+        Ok(self)
+    }
+
+    /// Specifies how variation parameters are chosen, replacing the default of
+    /// [`VariationStrategy::Automatic`].  Use [`VariationStrategy::CallerProvided`] to indicate
+    /// the type of variation parameter the caller is able to provide.
+    pub fn variation_strategy(mut self, strategy: VariationStrategy) -> Self {
+        let rune = Rune::VariationStrategy(strategy);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    pub fn security_bits(mut self, security_bits: u8) -> Self {
+        let rune = Rune::SecurityBits(security_bits);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires that the authentication tag or digest be at least `tag_bits` long.  See
+    /// [`Rune::MinTagBits`].
+    pub fn min_tag_bits(mut self, tag_bits: u16) -> Self {
+        let rune = Rune::MinTagBits(tag_bits);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires support for messages of up to `max_bytes` bytes each.  This limits the size of
+    /// each individual message, not the number of messages; many constructions have a maximum
+    /// message size, e.g. 2³⁶ bytes for AES-GCM because of its 32-bit block counter.  Equivalent
+    /// to [`SchemaBuilder::message_size_limit`].
+    pub fn per_message(self, max_bytes: u128) -> Result<Self> {
+        self.message_size_limit(max_bytes)
+    }
+
+    /// Requires support for sealing at least `count` messages with the key.  This limits the
+    /// number of messages, not their size; constructions with random nonces must be limited to
+    /// well below the birthday bound, e.g. 2³² messages for AES-GCM.  Equivalent to
+    /// [`SchemaBuilder::message_limit`].
+    pub fn per_key_messages(self, count: u128) -> Result<Self> {
+        self.message_limit(count)
+    }
+
+    /// Requires support for sealing at least `total` bytes, summed across all messages, with the
+    /// key.  Some constructions degrade with the total amount of data processed, regardless of
+    /// how it is split into messages, e.g. AES-CBC as ciphertext block collisions become likely.
+    /// Equivalent to [`SchemaBuilder::total_data_limit`].
+    pub fn per_key_bytes(self, total: u128) -> Result<Self> {
+        self.total_data_limit(total)
+    }
+
+    pub fn message_limit(mut self, message_limit: u128) -> Result<Self> {
+        if message_limit == u128::MAX {
+            return Err(Error::InvalidMessageLimit("Message limit cannot be unbounded".into()));
+        }
+        let rune = Rune::MessageLimit(message_limit);
+        self.runes.insert(rune.variant_index(), rune);
+        Ok(self)
+    }
+
+    pub fn enforced_message_limit(mut self, enforced_message_limit: u128) -> Result<Self> {
+        if enforced_message_limit == u128::MAX {
+            return Err(Error::InvalidMessageLimit("Message limit cannot be unbounded".into()));
+        }
+        let rune = Rune::EnforcedMessageLimit(enforced_message_limit);
+        self.runes.insert(rune.variant_index(), rune);
+        Ok(self)
+    }
+
+    /// Caps the number of messages the key may be able to process.  Unlike the other limits,
+    /// this is an upper bound; see [`Rune::MaxEnforcedMessageLimit`].
+    pub fn max_enforced_message_limit(mut self, max_enforced_message_limit: u128) -> Self {
+        let rune = Rune::MaxEnforcedMessageLimit(max_enforced_message_limit);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    pub fn message_size_limit(mut self, message_size_limit: u128) -> Result<Self> {
+        if message_size_limit == u128::MAX {
+            return Err(Error::InvalidMessageSizeLimit(
+                "Message size limit cannot be unbounded".into(),
+            ));
+        }
         let rune = Rune::MessageSizeLimit(message_size_limit);
         self.runes.insert(rune.variant_index(), rune);
-        Ok(self)
+        Ok(self)
+    }
+
+    pub fn enforced_message_size_limit(
+        mut self,
+        enforced_message_size_limit: u128,
+    ) -> Result<Self> {
+        if enforced_message_size_limit == u128::MAX {
+            return Err(Error::InvalidMessageSizeLimit(
+                "Message size limit cannot be unbounded".into(),
+            ));
+        }
+        let rune = Rune::EnforcedMessageSizeLimit(enforced_message_size_limit);
+        self.runes.insert(rune.variant_index(), rune);
+        Ok(self)
+    }
+
+    pub fn total_data_limit(mut self, total_data_limit: u128) -> Result<Self> {
+        if total_data_limit == u128::MAX {
+            return Err(Error::InvalidTotalDataLimit(
+                "Total data limit cannot be unbounded".into(),
+            ));
+        }
+        let rune = Rune::TotalDataLimit(total_data_limit);
+        self.runes.insert(rune.variant_index(), rune);
+        Ok(self)
+    }
+
+    pub fn enforced_total_data_limit(mut self, enforced_total_data_limit: u128) -> Result<Self> {
+        if enforced_total_data_limit == u128::MAX {
+            return Err(Error::InvalidTotalDataLimit(
+                "Total data limit cannot be unbounded".into(),
+            ));
+        }
+        let rune = Rune::EnforcedTotalDataLimit(enforced_total_data_limit);
+        self.runes.insert(rune.variant_index(), rune);
+        Ok(self)
+    }
+
+    pub fn crypto_period(mut self, begin: Zoned, end: Zoned) -> Result<Self> {
+        let CryptoPeriod { begin, end } = CryptoPeriod::new(begin, end)?;
+        let rune = Rune::CryptoPeriod { begin, end };
+        self.runes.insert(rune.variant_index(), rune);
+        Ok(self)
+    }
+
+    pub fn quantum_resistance(mut self, quantum_resistance: bool) -> Self {
+        if quantum_resistance {
+            let rune = Rune::QuantumResistance;
+            self.runes.insert(rune.variant_index(), rune);
+        } else {
+            self.runes.remove(&Rune::QuantumResistance.variant_index());
+        }
+        self
+    }
+
+    pub fn software_side_channel_resistance(
+        mut self,
+        resistance: SoftwareSideChannelResistance,
+    ) -> Self {
+        push_to_vec_rune!(self.runes, SoftwareSideChannelResistance, resistance);
+        self
+    }
+
+    pub fn hardware_side_channel_resistance(
+        mut self,
+        resistance: HardwareSideChannelResistance,
+    ) -> Self {
+        push_to_vec_rune!(self.runes, HardwareSideChannelResistance, resistance);
+        self
+    }
+
+    pub fn isolated(mut self, isolation_level: IsolationLevel) -> Self {
+        let rune = Rune::Isolated(isolation_level);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    pub fn certification(mut self, certification: SecurityCertification) -> Self {
+        push_to_vec_rune!(self.runes, Certifications, certification);
+        self
+    }
+
+    /// Permits the [`crate::provider::BindRune`] to be used for `usage`.  Once any usage is
+    /// specified, the `BindRune` is restricted to the specified usages.  See [`Rune::KeyUsage`].
+    pub fn key_usage(mut self, usage: KeyUsage) -> Self {
+        push_to_vec_rune!(self.runes, KeyUsage, usage);
+        self
+    }
+
+    /// Requires that every use of the [`crate::provider::BindRune`] be recorded in an audit log.
+    pub fn audit_logged(mut self) -> Self {
+        let rune = Rune::AuditLogged;
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires that the [`crate::provider::BindRune`] reject seal operations with rolled-back
+    /// versions.  See [`Rune::AntiRollback`].
+    pub fn anti_rollback(mut self) -> Self {
+        let rune = Rune::AntiRollback;
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Limits the [`crate::provider::BindRune`] to at most `max_depth` derivation steps from its
+    /// root.  See [`Rune::DerivationDepth`].
+    pub fn max_derivation_depth(mut self, max_depth: u8) -> Self {
+        let rune = Rune::DerivationDepth(max_depth);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires the extension rune `id` with the encoded `value`.  See [`Rune::Extension`].
+    pub fn extension(mut self, id: u16, value: Vec<u8>) -> Self {
+        let rune = Rune::Extension { id, value };
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires a construction providing forward secrecy.  See [`Rune::ForwardSecrecy`].
+    pub fn forward_secrecy(mut self) -> Self {
+        let rune = Rune::ForwardSecrecy;
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires a nonce-misuse resistant construction.  See [`Rune::NonceMisuseResistance`].
+    pub fn nonce_misuse_resistance(mut self) -> Self {
+        let rune = Rune::NonceMisuseResistance;
+        self.runes.insert(rune.variant_index(), rune);
+        self
     }
 
-    pub fn enforced_message_size_limit(
-        mut self,
-        enforced_message_size_limit: u128,
-    ) -> Result<Self> {
-        if enforced_message_size_limit == u128::MAX {
-            return Err(Error::InvalidMessageSizeLimit(format!(
-                "Message size limit cannot be unbounded"
-            )));
-        }
-        let rune = Rune::EnforcedMessageSizeLimit(enforced_message_size_limit);
+    /// Requires that the provider can attest to the key in the specified format.  See
+    /// [`Rune::Attestation`].
+    pub fn attestation(mut self, format: AttestationFormat) -> Self {
+        let rune = Rune::Attestation(format);
         self.runes.insert(rune.variant_index(), rune);
-        Ok(self)
+        self
     }
 
-    pub fn total_data_limit(mut self, total_data_limit: u128) -> Result<Self> {
-        if total_data_limit == u128::MAX {
-            return Err(Error::InvalidTotalDataLimit(format!(
-                "Total data limit cannot be unbounded"
-            )));
+    /// Requires that the key be rotated at least every `interval`.  See
+    /// [`Rune::KeyRotationInterval`].  Days are taken to be 24 hours long.  Fails with
+    /// [`Error::InvalidCryptoPeriod`] if `interval` is not positive, or if it contains months or
+    /// years, whose length varies; express such intervals in days instead.
+    pub fn key_rotation_interval(mut self, interval: Span) -> Result<Self> {
+        let interval =
+            interval.to_duration(SpanRelativeTo::days_are_24_hours()).map_err(|error| {
+                Error::InvalidCryptoPeriod(format!("Invalid key rotation interval: {}", error))
+            })?;
+        if !interval.is_positive() {
+            return Err(Error::InvalidCryptoPeriod(
+                "Key rotation interval must be positive".into(),
+            ));
         }
-        let rune = Rune::TotalDataLimit(total_data_limit);
+        let rune = Rune::KeyRotationInterval(interval);
         self.runes.insert(rune.variant_index(), rune);
         Ok(self)
     }
 
-    pub fn enforced_total_data_limit(mut self, enforced_total_data_limit: u128) -> Result<Self> {
-        if enforced_total_data_limit == u128::MAX {
-            return Err(Error::InvalidTotalDataLimit(format!(
-                "Total data limit cannot be unbounded"
-            )));
-        }
-        let rune = Rune::EnforcedTotalDataLimit(enforced_total_data_limit);
+    /// Requires that key material be generated from `source`, or a stronger source.  See
+    /// [`Rune::EntropySource`].
+    pub fn entropy_source(mut self, source: EntropySource) -> Self {
+        let rune = Rune::EntropySource(source);
         self.runes.insert(rune.variant_index(), rune);
-        Ok(self)
+        self
     }
 
-    pub fn crypto_period(mut self, begin: Zoned, end: Zoned) -> Result<Self> {
-        if begin >= end {
-            return Err(Error::InvalidCryptoPeriod(format!(
-                "Begin {} must be before end {}",
-                begin, end
+    /// Requires a memory-hard password-based KDF with at least the costs in `params`.  See
+    /// [`Rune::MemoryHardness`].
+    pub fn memory_hardness(mut self, params: CostParameters) -> Self {
+        let rune = Rune::MemoryHardness(params);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires a memory-hard password-based KDF using at least `min_memory_kib` KiB of memory,
+    /// with any number of iterations.  The other cost parameters are filled in so as not to
+    /// constrain the choice: `iterations` is 1, the smallest valid value, so every KDF meets it,
+    /// and `parallelism` is 1, though it is never compared.  To require a number of iterations as
+    /// well, use [`SchemaBuilder::memory_hardness`] with the full [`CostParameters`].
+    pub fn min_memory_kib(self, min_memory_kib: u32) -> Self {
+        self.memory_hardness(CostParameters {
+            memory_kib: min_memory_kib,
+            iterations: 1,
+            parallelism: 1,
+        })
+    }
+
+    /// Requires a key split among at least `n` parties, any `t` of whom can use it.  See
+    /// [`Rune::Threshold`].  Fails with [`Error::InvalidParameter`] unless `1 <= t <= n`.
+    pub fn threshold(mut self, t: u16, n: u16) -> Result<Self> {
+        if t == 0 || t > n {
+            return Err(Error::InvalidParameter(format!(
+                "Invalid threshold {} of {}; the threshold must be between 1 and the number of \
+                 parties",
+                t, n
             )));
         }
-        let rune = Rune::CryptoPeriod { begin, end };
+        let rune = Rune::Threshold { t, n };
         self.runes.insert(rune.variant_index(), rune);
         Ok(self)
     }
 
-    pub fn quantum_resistance(mut self, quantum_resistance: bool) -> Self {
-        if quantum_resistance {
-            let rune = Rune::QuantumResistance;
-            self.runes.insert(rune.variant_index(), rune);
-        } else {
-            self.runes.remove(&Rune::QuantumResistance.variant_index());
-        }
+    /// Requires that a payload can be sealed to up to `max` recipients at once.  See
+    /// [`Rune::MultiRecipient`].
+    pub fn multi_recipient(mut self, max: u32) -> Self {
+        let rune = Rune::MultiRecipient { max };
+        self.runes.insert(rune.variant_index(), rune);
         self
     }
 
-    pub fn software_side_channel_resistance(
-        mut self,
-        resistance: SoftwareSideChannelResistance,
-    ) -> Self {
-        push_to_vec_rune!(self.runes, SoftwareSideChannelResistance, resistance);
+    /// Requires a key agreement with contributory behavior.  See
+    /// [`Rune::ContributoryKeyAgreement`].
+    pub fn contributory_key_agreement(mut self) -> Self {
+        let rune = Rune::ContributoryKeyAgreement;
+        self.runes.insert(rune.variant_index(), rune);
         self
     }
 
-    pub fn hardware_side_channel_resistance(
-        mut self,
-        resistance: HardwareSideChannelResistance,
-    ) -> Self {
-        push_to_vec_rune!(self.runes, HardwareSideChannelResistance, resistance);
+    /// Requires a deterministic construction.  See [`Rune::Deterministic`].
+    pub fn deterministic(mut self) -> Self {
+        let rune = Rune::Deterministic;
+        self.runes.insert(rune.variant_index(), rune);
         self
     }
 
-    pub fn isolated(mut self, isolation_level: IsolationLevel) -> Self {
-        let rune = Rune::Isolated(isolation_level);
+    /// Requires verifiable usage proofs.  See [`Rune::UsageProof`].
+    pub fn usage_proof(mut self) -> Self {
+        let rune = Rune::UsageProof;
         self.runes.insert(rune.variant_index(), rune);
         self
     }
 
-    pub fn certification(mut self, certification: SecurityCertification) -> Self {
-        push_to_vec_rune!(self.runes, Certifications, certification);
+    /// Requires a key-committing construction.  See [`Rune::KeyCommitment`].
+    pub fn key_commitment(mut self) -> Self {
+        let rune = Rune::KeyCommitment;
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires an independently auditable implementation.  See [`Rune::OpenImplementation`].
+    pub fn open_implementation(mut self) -> Self {
+        let rune = Rune::OpenImplementation;
+        self.runes.insert(rune.variant_index(), rune);
         self
     }
+
+    /// Binds the [`crate::provider::BindRune`] to the specified application domain, so that data
+    /// sealed with it cannot be unsealed by a `BindRune` bound to a different domain.
+    pub fn domain_separation(mut self, domain: &str) -> Self {
+        let rune = Rune::DomainSeparation(domain.to_string());
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Sets the escrow policy of the [`crate::provider::BindRune`].  See [`Rune::Escrow`].
+    pub fn escrow(mut self, policy: EscrowPolicy) -> Self {
+        let rune = Rune::Escrow(policy);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+
+    /// Requires that the key material of the [`crate::provider::BindRune`] has the specified
+    /// origin.
+    pub fn key_origin(mut self, origin: KeyOrigin) -> Self {
+        let rune = Rune::KeyOrigin(origin);
+        self.runes.insert(rune.variant_index(), rune);
+        self
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Compares extension values as big-endian integers of equal length, so that higher values
+    /// exceed lower ones.
+    pub(crate) struct AtLeast;
+
+    impl RuneComparator for AtLeast {
+        fn coverage(&self, required: &[u8], provided: &[u8]) -> Coverage {
+            Coverage::compare(provided, required)
+        }
+    }
+
+    #[test]
+    fn coverage_reports_exceeded_and_unmet_axes() {
+        let baseline = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+        let schema = SchemaBuilder::new().security_bits(192).build();
+
+        let report = schema.coverage(&baseline);
+
+        let coverage = |rune: &Rune| {
+            report
+                .entries()
+                .iter()
+                .find(|entry| entry.requirement.variant_index() == rune.variant_index())
+                .map(|entry| entry.coverage)
+        };
+        assert_eq!(coverage(&Rune::SecurityBits(0)), Some(Coverage::Exceeded));
+        assert_eq!(coverage(&Rune::QuantumResistance), Some(Coverage::Unmet));
+        assert_eq!(coverage(&Rune::MessageLimit(0)), Some(Coverage::Met));
+        assert_eq!(report.entries().len(), baseline.runes().len());
+        assert!(!report.is_satisfied());
+        assert_eq!(report.unmet().collect::<Vec<_>>(), [&Rune::QuantumResistance]);
+    }
+
+    #[test]
+    fn coverage_ignores_runes_absent_from_baseline() {
+        let baseline = SchemaBuilder::new().security_bits(128).build();
+        let schema = SchemaBuilder::new().security_bits(128).audit_logged().build();
+
+        let report = schema.coverage(&baseline);
+
+        assert!(report.is_satisfied());
+        assert!(report.entries().iter().all(|entry| entry.requirement != Rune::AuditLogged));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn coverage_report_round_trips_through_serde() {
+        let baseline = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+        let report = SchemaBuilder::new().security_bits(192).build().coverage(&baseline);
+
+        let bytes = cbor4ii::serde::to_vec(Vec::new(), &report).unwrap();
+        let decoded: CoverageReport = cbor4ii::serde::from_slice(&bytes).unwrap();
+
+        assert_eq!(decoded, report);
+        assert!(decoded.entries().iter().any(|entry| entry.coverage == Coverage::Exceeded));
+        assert_eq!(decoded.unmet().collect::<Vec<_>>(), [&Rune::QuantumResistance]);
+    }
+
+    #[test]
+    fn delta_contains_only_stricter_requirements() {
+        let key = SchemaBuilder::new().security_bits(128).build();
+        let policy = SchemaBuilder::new().security_bits(192).quantum_resistance(true).build();
+
+        let delta = key.delta(&policy);
+
+        assert_eq!(delta.runes(), [Rune::SecurityBits(192), Rune::QuantumResistance]);
+        assert!(key.delta(&key).runes().is_empty());
+        assert!(policy.delta(&key).runes().is_empty());
+    }
+
+    fn canonical_schema() -> Schema {
+        SchemaBuilder::new()
+            .security_bits(128)
+            .quantum_resistance(true)
+            .key_usage(KeyUsage::Encrypt)
+            .key_usage(KeyUsage::Decrypt)
+            .deny_construction(ConstructionIdentifier::new("a"))
+            .deny_construction(ConstructionIdentifier::new("b"))
+            .build()
+    }
+
+    #[test]
+    fn builder_produces_canonical_schemas_in_any_order() {
+        let reordered = SchemaBuilder::new()
+            .deny_construction(ConstructionIdentifier::new("b"))
+            .key_usage(KeyUsage::Decrypt)
+            .quantum_resistance(true)
+            .key_usage(KeyUsage::Encrypt)
+            .key_usage(KeyUsage::Decrypt)
+            .deny_construction(ConstructionIdentifier::new("a"))
+            .security_bits(128)
+            .build();
+
+        assert!(reordered.is_canonical());
+        assert_eq!(reordered, canonical_schema());
+        assert!(reordered.runes().windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn merge_produces_canonical_schemas() {
+        let merged = SchemaBuilder::new()
+            .deny_construction(ConstructionIdentifier::new("b"))
+            .key_usage(KeyUsage::Encrypt)
+            .key_usage(KeyUsage::Decrypt)
+            .build()
+            .merge(
+                &SchemaBuilder::new()
+                    .quantum_resistance(true)
+                    .security_bits(128)
+                    .deny_construction(ConstructionIdentifier::new("a"))
+                    .build(),
+            )
+            .unwrap();
+
+        assert!(merged.is_canonical());
+        assert_eq!(merged, canonical_schema());
+    }
+
+    #[test]
+    fn merge_takes_the_stricter_requirement() {
+        let policy = SchemaBuilder::new()
+            .security_bits(192)
+            .key_usage(KeyUsage::Encrypt)
+            .key_usage(KeyUsage::Decrypt)
+            .escrow(EscrowPolicy::Forbidden)
+            .build();
+        let request = SchemaBuilder::new()
+            .security_bits(128)
+            .quantum_resistance(true)
+            .key_usage(KeyUsage::Decrypt)
+            .key_usage(KeyUsage::Sign)
+            .escrow(EscrowPolicy::Allowed)
+            .build();
+
+        let merged = policy.merge(&request).unwrap();
+
+        assert_eq!(
+            merged,
+            SchemaBuilder::new()
+                .security_bits(192)
+                .quantum_resistance(true)
+                .key_usage(KeyUsage::Decrypt)
+                .escrow(EscrowPolicy::Forbidden)
+                .build()
+        );
+        assert_eq!(request.merge(&policy).unwrap(), merged);
+    }
+
+    #[test]
+    fn merge_rejects_irreconcilable_requirements() {
+        let fips = |level| SecurityCertification { scheme: CertificationScheme::Fips140_3, level };
+        let common_criteria = SecurityCertification {
+            scheme: CertificationScheme::CommonCriteria,
+            level: CertificationLevel::Level4,
+        };
+        let conflicting = [
+            (
+                SchemaBuilder::new().escrow(EscrowPolicy::Required),
+                SchemaBuilder::new().escrow(EscrowPolicy::Forbidden),
+            ),
+            (
+                SchemaBuilder::new().certification(fips(CertificationLevel::Level2)),
+                SchemaBuilder::new().certification(common_criteria),
+            ),
+            (
+                SchemaBuilder::new().key_usage(KeyUsage::Sign),
+                SchemaBuilder::new().key_usage(KeyUsage::Encrypt),
+            ),
+            (
+                SchemaBuilder::new().domain_separation("backups"),
+                SchemaBuilder::new().domain_separation("messages"),
+            ),
+            (
+                SchemaBuilder::new().key_origin(KeyOrigin::Generated),
+                SchemaBuilder::new().key_origin(KeyOrigin::Imported),
+            ),
+            (
+                SchemaBuilder::new().attestation(AttestationFormat::TpmQuote),
+                SchemaBuilder::new().attestation(AttestationFormat::FidoU2f),
+            ),
+        ];
+
+        for (a, b) in conflicting {
+            let (a, b) = (a.build(), b.build());
+            assert!(matches!(a.merge(&b), Err(Error::ConflictingRequirements(_))), "{:?}", a);
+            assert!(a.merge(&a).is_ok());
+        }
+    }
+
+    #[test]
+    fn merge_widens_caller_provided_variations_to_arbitrary() {
+        let caller = |variation| {
+            SchemaBuilder::new()
+                .variation_strategy(VariationStrategy::CallerProvided(variation))
+                .build()
+        };
+        let automatic =
+            SchemaBuilder::new().variation_strategy(VariationStrategy::Automatic).build();
+
+        assert_eq!(
+            caller(VariationType::Random).merge(&caller(VariationType::Unique)).unwrap(),
+            caller(VariationType::Arbitrary)
+        );
+        assert_eq!(caller(VariationType::Unique).merge(&automatic).unwrap(), automatic);
+    }
+
+    #[test]
+    fn merge_keeps_this_schemas_preference_on_conflict() {
+        let prefer = |domain| {
+            SchemaBuilder::new().prefer(Rune::DomainSeparation(String::from(domain))).build()
+        };
+
+        assert_eq!(prefer("backups").merge(&prefer("messages")).unwrap(), prefer("backups"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialization_canonicalizes_schemas() {
+        #[derive(serde::Serialize)]
+        struct Raw {
+            runes: Vec<Rune>,
+            preferences: Vec<Rune>,
+            denied: Vec<ConstructionIdentifier>,
+        }
+
+        let mut runes = canonical_schema().runes().to_vec();
+        runes.reverse();
+        runes.push(Rune::KeyUsage(vec![KeyUsage::Encrypt, KeyUsage::Decrypt, KeyUsage::Encrypt]));
+        let raw = Raw {
+            runes,
+            preferences: Vec::new(),
+            denied: vec![ConstructionIdentifier::new("b"), ConstructionIdentifier::new("a")],
+        };
+        let bytes = cbor4ii::serde::to_vec(Vec::new(), &raw).unwrap();
+
+        let schema: Schema = cbor4ii::serde::from_slice(&bytes).unwrap();
+
+        assert!(schema.is_canonical());
+        assert_eq!(schema, canonical_schema());
+        let bytes = cbor4ii::serde::to_vec(Vec::new(), &schema).unwrap();
+        assert_eq!(cbor4ii::serde::from_slice::<Schema>(&bytes).unwrap(), schema);
+    }
+
+    #[test]
+    fn aead_until_aligns_all_three_horizons() {
+        let schema = SchemaBuilder::new().aead_until(OriginIdentity, 2040).unwrap().build();
+
+        let confidentiality_end = schema.runes().iter().find_map(|rune| match rune {
+            Rune::Confidentiality { end_time } => Some(*end_time),
+            _ => None,
+        });
+        assert_eq!(confidentiality_end, Some(end_of_year(2040).unwrap()));
+        assert!(schema.runes().contains(&Rune::Integrity { year: 2040 }));
+        assert!(
+            schema.runes().contains(&Rune::Authentication { origin: OriginIdentity, year: 2040 })
+        );
+    }
+
+    #[test]
+    fn escrow_policies_must_not_contradict() {
+        let escrow = |policy| SchemaBuilder::new().escrow(policy).build();
+        let (required, forbidden, allowed) = (
+            escrow(EscrowPolicy::Required),
+            escrow(EscrowPolicy::Forbidden),
+            escrow(EscrowPolicy::Allowed),
+        );
+
+        assert!(required.satisfies(&required));
+        assert!(!forbidden.satisfies(&required));
+        assert!(!required.satisfies(&forbidden));
+        assert!(allowed.satisfies(&forbidden));
+        assert!(forbidden.satisfies(&allowed));
+    }
+
+    #[test]
+    fn limit_helpers_set_the_matching_runes() {
+        let helper = |configure: fn(SchemaBuilder) -> Result<SchemaBuilder>| {
+            configure(SchemaBuilder::new()).unwrap().build()
+        };
+
+        let per_message = helper(|builder| builder.per_message(1024));
+        let per_key_messages = helper(|builder| builder.per_key_messages(1 << 20));
+        let per_key_bytes = helper(|builder| builder.per_key_bytes(1 << 40));
+
+        assert_eq!(
+            per_message.get(Rune::MessageSizeLimit(0).variant_index()),
+            Some(&Rune::MessageSizeLimit(1024))
+        );
+        assert_eq!(
+            per_key_messages.get(Rune::MessageLimit(0).variant_index()),
+            Some(&Rune::MessageLimit(1 << 20))
+        );
+        assert_eq!(
+            per_key_bytes.get(Rune::TotalDataLimit(0).variant_index()),
+            Some(&Rune::TotalDataLimit(1 << 40))
+        );
+        assert_eq!(per_message, helper(|builder| builder.message_size_limit(1024)));
+        assert_eq!(per_key_messages, helper(|builder| builder.message_limit(1 << 20)));
+        assert_eq!(per_key_bytes, helper(|builder| builder.total_data_limit(1 << 40)));
+        assert!(matches!(
+            SchemaBuilder::new().per_message(u128::MAX),
+            Err(Error::InvalidMessageSizeLimit(_))
+        ));
+    }
+
+    #[test]
+    fn max_message_bytes_prefers_enforced_limit() {
+        let enforced = SchemaBuilder::new()
+            .message_size_limit(4096)
+            .unwrap()
+            .enforced_message_size_limit(1024)
+            .unwrap()
+            .build();
+        let requested = SchemaBuilder::new().message_size_limit(4096).unwrap().build();
+        let unbounded = Schema {
+            runes: vec![Rune::EnforcedMessageSizeLimit(u128::MAX)],
+            preferences: Vec::new(),
+            denied: Vec::new(),
+        };
+
+        assert_eq!(enforced.max_message_bytes(), Some(1024));
+        assert_eq!(requested.max_message_bytes(), Some(4096));
+        assert_eq!(unbounded.max_message_bytes(), None);
+    }
+
+    #[test]
+    fn signing_preset_is_sign_only_with_authentication() {
+        let schema = SchemaBuilder::for_signing(OriginIdentity, 2035).build();
+
+        assert!(schema.is_asymmetric());
+        assert_eq!(
+            schema.get(Rune::KeyUsage(Vec::new()).variant_index()),
+            Some(&Rune::KeyUsage(vec![KeyUsage::Sign, KeyUsage::Verify]))
+        );
+        assert!(
+            schema.runes().contains(&Rune::Authentication { origin: OriginIdentity, year: 2035 })
+        );
+        assert!(schema.runes().contains(&Rune::Integrity { year: 2035 }));
+        assert!(!schema.runes().iter().any(|rune| matches!(rune, Rune::Confidentiality { .. })));
+        assert!(schema.check_usage(KeyUsage::Encrypt).is_err());
+    }
+
+    #[test]
+    fn encryption_preset_has_confidentiality() {
+        let schema = SchemaBuilder::for_encryption(2035).unwrap().build();
+
+        assert!(schema.is_symmetric_only());
+        assert!(
+            schema
+                .runes()
+                .contains(&Rune::Confidentiality { end_time: end_of_year(2035).unwrap() })
+        );
+        assert!(schema.check_usage(KeyUsage::Decrypt).is_ok());
+        assert!(schema.check_usage(KeyUsage::Sign).is_err());
+    }
+
+    #[test]
+    fn over_provisioning_reports_excess_security_bits() {
+        let requirement = SchemaBuilder::new().security_bits(128).build();
+        let provided = SchemaBuilder::new().security_bits(192).build();
+
+        assert_eq!(
+            provided.over_provisioning(&requirement),
+            [(Rune::SecurityBits(128), Rune::SecurityBits(192))]
+        );
+        assert!(
+            SchemaBuilder::new()
+                .security_bits(160)
+                .build()
+                .over_provisioning(&requirement)
+                .is_empty()
+        );
+        assert!(requirement.over_provisioning(&requirement).is_empty());
+    }
+
+    #[test]
+    fn requires_certification_reports_minimum_level_per_scheme() {
+        let fips = |level| SecurityCertification { scheme: CertificationScheme::Fips140_3, level };
+        let schema = SchemaBuilder::new()
+            .certification(fips(CertificationLevel::Level3))
+            .certification(fips(CertificationLevel::Level2))
+            .build();
+
+        assert_eq!(
+            schema.requires_certification(CertificationScheme::Fips140_3),
+            Some(CertificationLevel::Level2)
+        );
+        assert_eq!(schema.requires_certification(CertificationScheme::CommonCriteria), None);
+        assert_eq!(
+            SchemaBuilder::new().build().requires_certification(CertificationScheme::Fips140_3),
+            None
+        );
+    }
+
+    fn year(year: i16) -> Zoned {
+        jiff::civil::date(year, 1, 1).at(0, 0, 0, 0).to_zoned(jiff::tz::TimeZone::UTC).unwrap()
+    }
+
+    fn period(begin: i16, end: i16) -> CryptoPeriod {
+        CryptoPeriod::new(year(begin), year(end)).unwrap()
+    }
+
+    #[test]
+    fn disjoint_periods_do_not_overlap() {
+        let (early, late) = (period(2020, 2022), period(2022, 2024));
+
+        assert!(!early.overlaps(&late));
+        assert!(!late.overlaps(&early));
+        assert_eq!(early.intersection(&late), None);
+        assert!(early.contains(&year(2020)));
+        assert!(!early.contains(&year(2022)));
+        assert!(CryptoPeriod::new(year(2022), year(2022)).is_err());
+    }
+
+    #[test]
+    fn overlapping_periods_intersect() {
+        let (early, late) = (period(2020, 2023), period(2022, 2025));
+
+        assert!(early.overlaps(&late));
+        assert!(!early.encloses(&late));
+        assert_eq!(early.intersection(&late), Some(period(2022, 2023)));
+        assert_eq!(late.intersection(&early), Some(period(2022, 2023)));
+    }
+
+    #[test]
+    fn nested_periods_are_enclosed() {
+        let (outer, inner) = (period(2020, 2030), period(2022, 2024));
+
+        assert!(outer.encloses(&inner));
+        assert!(!inner.encloses(&outer));
+        assert!(outer.encloses(&outer));
+        assert_eq!(outer.intersection(&inner), Some(inner.clone()));
+    }
+
+    #[test]
+    fn key_commitment_is_required_explicitly() {
+        let requirements = SchemaBuilder::new().security_bits(128).key_commitment().build();
+        let gcm = SchemaBuilder::new().security_bits(128).build();
+        let committing = SchemaBuilder::new().security_bits(128).key_commitment().build();
+
+        assert!(!gcm.satisfies(&requirements));
+        assert!(committing.satisfies(&requirements));
+        assert!(committing.satisfies(&gcm));
+    }
+
+    #[test]
+    fn subkeys_are_one_level_deeper_than_their_parent() {
+        let depth = |depth| Schema {
+            runes: vec![Rune::DerivationDepth(depth)],
+            preferences: Vec::new(),
+            denied: Vec::new(),
+        };
+        let max_two = SchemaBuilder::new().max_derivation_depth(2).build();
+
+        assert_eq!(SchemaBuilder::new().build().child_derivation_depth(&max_two).unwrap(), 1);
+        assert_eq!(depth(1).child_derivation_depth(&max_two).unwrap(), 2);
+        assert!(matches!(
+            depth(2).child_derivation_depth(&max_two),
+            Err(Error::DerivationTooDeep(_))
+        ));
+        assert!(depth(1).satisfies(&depth(2)));
+        assert!(!depth(3).satisfies(&depth(2)));
+    }
+
+    #[test]
+    fn max_enforced_message_limit_caps_the_reported_limit() {
+        let schema = |runes| Schema { runes, preferences: Vec::new(), denied: Vec::new() };
+        let cap = schema(vec![Rune::MaxEnforcedMessageLimit(1 << 32)]);
+
+        assert!(!schema(vec![Rune::MessageLimit(1 << 64)]).satisfies(&cap));
+        assert!(schema(vec![Rune::EnforcedMessageLimit(1 << 20)]).satisfies(&cap));
+        assert!(schema(vec![Rune::MessageLimit(1 << 32)]).satisfies(&cap));
+        assert!(!schema(Vec::new()).satisfies(&cap));
+        assert_eq!(cap.merge(&schema(vec![Rune::MaxEnforcedMessageLimit(1 << 40)])).unwrap(), cap);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn fingerprints_hash_the_canonical_encoding() {
+        use sha2::Digest;
+
+        let reordered = SchemaBuilder::new()
+            .deny_construction(ConstructionIdentifier::new("b"))
+            .key_usage(KeyUsage::Decrypt)
+            .deny_construction(ConstructionIdentifier::new("a"))
+            .key_usage(KeyUsage::Encrypt)
+            .quantum_resistance(true)
+            .security_bits(128)
+            .build();
+        let stronger = SchemaBuilder::new().security_bits(192).build();
+        let encoding = cbor4ii::serde::to_vec(Vec::new(), &canonical_schema()).unwrap();
+
+        let fingerprint = canonical_schema().fingerprint();
+
+        assert_eq!(fingerprint, <[u8; 32]>::from(sha2::Sha256::digest(&encoding)));
+        assert_eq!(reordered.fingerprint(), fingerprint);
+        assert_ne!(canonical_schema().merge(&stronger).unwrap().fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn registered_comparators_assess_extensions() {
+        let level = |level| SchemaBuilder::new().extension(7, vec![level]).build();
+        let mut comparators = RuneComparators::new();
+        comparators.register(7, Box::new(AtLeast));
+
+        assert!(!level(3).satisfies(&level(2)));
+        assert!(level(3).satisfies_with(&level(2), &comparators));
+        assert!(!level(1).satisfies_with(&level(2), &comparators));
+        assert_eq!(
+            level(1).delta_with(&level(2), &comparators).runes(),
+            [Rune::Extension { id: 7, value: vec![2] }]
+        );
+        assert!(level(3).delta_with(&level(2), &comparators).runes().is_empty());
+        assert!(
+            SchemaBuilder::new()
+                .extension(8, vec![3])
+                .build()
+                .satisfies_with(&SchemaBuilder::new().extension(8, vec![3]).build(), &comparators)
+        );
+    }
+
+    #[test]
+    fn forward_secrecy_must_be_provided() {
+        let requirements = SchemaBuilder::new().security_bits(128).forward_secrecy().build();
+        let static_key = SchemaBuilder::new().security_bits(128).build();
+        let ephemeral = SchemaBuilder::new().security_bits(128).forward_secrecy().build();
+
+        assert!(!static_key.satisfies(&requirements));
+        assert!(ephemeral.satisfies(&requirements));
+        assert_eq!(static_key.delta(&requirements).runes(), [Rune::ForwardSecrecy]);
+    }
+
+    #[test]
+    fn satisfies_compares_each_axis() {
+        use SoftwareSideChannelResistance::{CacheTimingResistant, ConstantTime};
+        let requirements = SchemaBuilder::new()
+            .security_bits(128)
+            .message_limit(1 << 20)
+            .unwrap()
+            .software_side_channel_resistance(ConstantTime)
+            .quantum_resistance(true)
+            .build();
+        let capability = |bits, message_limit, resistances: &[SoftwareSideChannelResistance]| {
+            let mut builder = SchemaBuilder::new()
+                .security_bits(bits)
+                .enforced_message_limit(message_limit)
+                .unwrap()
+                .quantum_resistance(true);
+            for resistance in resistances {
+                builder = builder.software_side_channel_resistance(*resistance);
+            }
+            builder.build()
+        };
+
+        let candidates = [
+            (capability(192, 1 << 32, &[ConstantTime, CacheTimingResistant]), true),
+            (capability(128, 1 << 20, &[ConstantTime]), true),
+            (capability(112, 1 << 32, &[ConstantTime]), false),
+            (capability(128, 1 << 16, &[ConstantTime]), false),
+            (capability(128, 1 << 20, &[CacheTimingResistant]), false),
+            (SchemaBuilder::new().security_bits(192).build(), false),
+        ];
+
+        for (capability, expected) in candidates {
+            assert_eq!(capability.satisfies(&requirements), expected, "{:?}", capability);
+            assert_eq!(capability.coverage(&requirements).is_satisfied(), expected);
+        }
+    }
+
+    #[test]
+    fn nonce_misuse_resistance_must_be_provided() {
+        let requirements =
+            SchemaBuilder::new().security_bits(128).nonce_misuse_resistance().build();
+        let gcm = SchemaBuilder::new().security_bits(128).build();
+        let gcm_siv = SchemaBuilder::new().security_bits(128).nonce_misuse_resistance().build();
+
+        assert!(!gcm.satisfies(&requirements));
+        assert!(gcm_siv.satisfies(&requirements));
+        assert!(gcm_siv.satisfies(&SchemaBuilder::new().security_bits(128).build()));
+        assert_eq!(gcm.delta(&requirements).runes(), [Rune::NonceMisuseResistance]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn key_committing_alias_deserializes() {
+        let encoded = cbor4ii::serde::to_vec(Vec::new(), &"KeyCommitting").unwrap();
+
+        let rune: Rune = cbor4ii::serde::from_slice(&encoded).unwrap();
+
+        assert_eq!(rune, Rune::KeyCommitment);
+    }
+
+    struct Platform2030;
+
+    impl PlatformAbstractions for Platform2030 {
+        fn get_current_time() -> Zoned {
+            year(2030)
+        }
+    }
+
+    #[test]
+    fn integrity_and_authentication_horizons_are_compared() {
+        let requirements = SchemaBuilder::new()
+            .integrity_for::<Platform2030>(Span::new().years(10))
+            .unwrap()
+            .authentication(OriginIdentity, 2040)
+            .build();
+        assert!(requirements.runes().contains(&Rune::Integrity { year: 2040 }));
+        assert!(
+            requirements
+                .runes()
+                .contains(&Rune::Authentication { origin: OriginIdentity, year: 2040 })
+        );
+
+        let capability = |integrity, authentication| {
+            SchemaBuilder::new()
+                .integrity(integrity)
+                .authentication(OriginIdentity, authentication)
+                .build()
+        };
+        assert!(capability(2040, 2040).satisfies(&requirements));
+        assert!(capability(2050, 2045).satisfies(&requirements));
+        assert!(!capability(2039, 2040).satisfies(&requirements));
+        assert!(!capability(2040, 2039).satisfies(&requirements));
+    }
+
+    #[test]
+    fn variation_strategy_defaults_to_automatic() {
+        let automatic = Rune::VariationStrategy(VariationStrategy::Automatic);
+        let unique = VariationStrategy::CallerProvided(VariationType::Unique);
+        let default_request = SchemaBuilder::new().build();
+        let caller_request = SchemaBuilder::new().variation_strategy(unique.clone()).build();
+        assert!(default_request.runes().contains(&automatic));
+        assert!(!caller_request.runes().contains(&automatic));
+        assert!(caller_request.runes().contains(&Rune::VariationStrategy(unique.clone())));
+
+        let gcm = SchemaBuilder::new().variation_strategy(unique.clone()).build();
+        let siv = SchemaBuilder::new().build();
+        assert!(!gcm.satisfies(&default_request));
+        assert!(gcm.satisfies(&caller_request));
+        assert!(siv.satisfies(&default_request));
+        assert!(siv.satisfies(&caller_request));
+    }
+
+    #[test]
+    fn runes_are_found_by_variant_index() {
+        let schema = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+
+        assert_eq!(
+            schema.get(Rune::SecurityBits(0).variant_index()),
+            Some(&Rune::SecurityBits(128))
+        );
+        assert_eq!(
+            schema.get(Rune::QuantumResistance.variant_index()),
+            Some(&Rune::QuantumResistance)
+        );
+        assert_eq!(schema.get(Rune::ForwardSecrecy.variant_index()), None);
+        assert_eq!(
+            Rune::MessageLimit(1).variant_index(),
+            Rune::EnforcedMessageLimit(1).variant_index()
+        );
+
+        let iterated: Vec<&Rune> = (&schema).into_iter().collect();
+        assert_eq!(iterated, schema.runes().iter().collect::<Vec<_>>());
+        assert!(iterated.windows(2).all(|pair| pair[0].variant_index() < pair[1].variant_index()));
+    }
+
+    #[test]
+    fn runes_sort_by_variant_index_then_payload() {
+        let mut runes = vec![
+            Rune::SecurityBits(192),
+            Rune::EnforcedMessageLimit(1 << 10),
+            Rune::QuantumResistance,
+            Rune::MessageLimit(1 << 20),
+            Rune::SecurityBits(128),
+            Rune::Extension { id: 1, value: vec![2] },
+            Rune::Extension { id: 1, value: vec![1, 9] },
+        ];
+        runes.sort();
+
+        assert_eq!(
+            runes,
+            [
+                Rune::SecurityBits(128),
+                Rune::SecurityBits(192),
+                Rune::MessageLimit(1 << 20),
+                Rune::EnforcedMessageLimit(1 << 10),
+                Rune::QuantumResistance,
+                Rune::Extension { id: 1, value: vec![1, 9] },
+                Rune::Extension { id: 1, value: vec![2] },
+            ]
+        );
+        let schema = SchemaBuilder::new().security_bits(128).quantum_resistance(true).build();
+        assert!(schema.runes().is_sorted());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn crypto_periods_round_trip_through_serde() {
+        let period = period(2030, 2040);
+        let rune = Rune::CryptoPeriod { begin: period.begin.clone(), end: period.end.clone() };
+
+        let bytes = cbor4ii::serde::to_vec(Vec::new(), &period).unwrap();
+        assert_eq!(cbor4ii::serde::from_slice::<CryptoPeriod>(&bytes).unwrap(), period);
+        let bytes = cbor4ii::serde::to_vec(Vec::new(), &rune).unwrap();
+        assert_eq!(cbor4ii::serde::from_slice::<Rune>(&bytes).unwrap(), rune);
+    }
+
+    #[test]
+    fn attestation_requires_the_same_format() {
+        let attested = |format| SchemaBuilder::new().attestation(format).build();
+        let requirements = attested(AttestationFormat::TpmQuote);
+
+        assert!(attested(AttestationFormat::TpmQuote).satisfies(&requirements));
+        assert!(!attested(AttestationFormat::AndroidKeystore).satisfies(&requirements));
+        assert!(!SchemaBuilder::new().build().satisfies(&requirements));
+        assert!(
+            attested(AttestationFormat::Custom("acme".into()))
+                .satisfies(&attested(AttestationFormat::Custom("acme".into())))
+        );
+        assert!(
+            !attested(AttestationFormat::Custom("acme".into()))
+                .satisfies(&attested(AttestationFormat::Custom("other".into())))
+        );
+    }
+
+    #[test]
+    fn key_rotation_interval_requires_rotating_at_least_as_often() {
+        let rotated =
+            |days: i64| SchemaBuilder::new().key_rotation_interval(Span::new().days(days)).unwrap();
+        let requirements = rotated(90).build();
+
+        assert_eq!(
+            requirements.get(Rune::KeyRotationInterval(SignedDuration::ZERO).variant_index()),
+            Some(&Rune::KeyRotationInterval(SignedDuration::from_hours(90 * 24)))
+        );
+        assert!(rotated(30).build().satisfies(&requirements));
+        assert!(rotated(90).build().satisfies(&requirements));
+        assert!(!rotated(365).build().satisfies(&requirements));
+        assert!(matches!(
+            SchemaBuilder::new().key_rotation_interval(Span::new().months(3)),
+            Err(Error::InvalidCryptoPeriod(_))
+        ));
+        assert!(matches!(
+            SchemaBuilder::new().key_rotation_interval(Span::new()),
+            Err(Error::InvalidCryptoPeriod(_))
+        ));
+    }
+
+    #[test]
+    fn try_build_rejects_conflicting_requirements() {
+        let five_years =
+            || SchemaBuilder::new().confidentiality::<Platform2030>(Span::new().years(5)).unwrap();
+        let conflicts = |builder: SchemaBuilder| {
+            matches!(builder.try_build(), Err(Error::ConflictingRequirements(_)))
+        };
+
+        assert!(five_years().security_bits(128).try_build().is_ok());
+        assert!(conflicts(five_years().security_bits(112)));
+        assert!(conflicts(SchemaBuilder::new().security_bits(112).integrity(2031)));
+        assert!(SchemaBuilder::new().security_bits(112).integrity(2030).try_build().is_ok());
+        assert!(conflicts(five_years().crypto_period(year(2030), year(2040)).unwrap()));
+        assert!(five_years().crypto_period(year(2030), year(2032)).unwrap().try_build().is_ok());
+        assert!(conflicts(
+            SchemaBuilder::new()
+                .message_limit(1 << 20)
+                .unwrap()
+                .max_enforced_message_limit(1 << 16)
+        ));
+    }
+
+    #[test]
+    fn diff_reports_each_differing_axis() {
+        let requested = SchemaBuilder::new().security_bits(128).build();
+        let provided = SchemaBuilder::new().security_bits(192).quantum_resistance(true).build();
+
+        let diff = requested.diff(&provided);
+
+        assert!(requested.diff(&requested).is_empty());
+        assert_eq!(diff.entries().len(), 2);
+        assert_eq!(
+            diff.get(Rune::SecurityBits(0).variant_index()),
+            Some(&RuneDifference {
+                this: Some(Rune::SecurityBits(128)),
+                other: Some(Rune::SecurityBits(192)),
+                delta: Some(64),
+            })
+        );
+        assert_eq!(
+            diff.get(Rune::QuantumResistance.variant_index()),
+            Some(&RuneDifference { this: None, other: Some(Rune::QuantumResistance), delta: None })
+        );
+        assert_eq!(
+            provided.diff(&requested).get(Rune::SecurityBits(0).variant_index()).unwrap().delta,
+            Some(-64)
+        );
+        assert_eq!(signed_delta(u128::MAX, 0), i128::MIN);
+        assert_eq!(signed_delta(0, u128::MAX), i128::MAX);
+    }
+
+    #[test]
+    fn builder_merge_combines_requirements_preferences_and_denials() {
+        let library = SchemaBuilder::new()
+            .security_bits(128)
+            .key_usage(KeyUsage::Encrypt)
+            .key_usage(KeyUsage::Decrypt)
+            .prefer_quantum_resistance()
+            .deny_construction(ConstructionIdentifier::new("des"));
+        let caller = SchemaBuilder::new()
+            .security_bits(192)
+            .key_usage(KeyUsage::Decrypt)
+            .prefer(Rune::DomainSeparation(String::from("caller")))
+            .deny_construction(ConstructionIdentifier::new("rc4"));
+
+        let merged = library.merge(caller).unwrap().build();
+
+        assert!(merged.runes().contains(&Rune::SecurityBits(192)));
+        assert!(merged.runes().contains(&Rune::KeyUsage(vec![KeyUsage::Decrypt])));
+        assert_eq!(
+            merged.preferences(),
+            [Rune::QuantumResistance, Rune::DomainSeparation(String::from("caller"))]
+        );
+        assert!(merged.denies(&ConstructionIdentifier::new("des")));
+        assert!(merged.denies(&ConstructionIdentifier::new("rc4")));
+        assert!(merged.is_canonical());
+
+        let result = SchemaBuilder::new()
+            .domain_separation("backups")
+            .merge(SchemaBuilder::new().domain_separation("messages"));
+        assert!(matches!(result, Err(Error::ConflictingRequirements(_))));
+    }
+
+    #[test]
+    fn entropy_source_is_met_by_stronger_sources() {
+        use EntropySource::{Drbg800_90A, HardwareTrng, Unspecified, Validated800_90B};
+        let source = |source| SchemaBuilder::new().entropy_source(source).build();
+        let requirements = source(Drbg800_90A);
+
+        assert!(source(Validated800_90B).satisfies(&requirements));
+        assert!(source(Drbg800_90A).satisfies(&requirements));
+        assert!(!source(HardwareTrng).satisfies(&requirements));
+        assert!(!source(Unspecified).satisfies(&requirements));
+        assert_eq!(
+            source(HardwareTrng).merge(&source(Validated800_90B)).unwrap(),
+            source(Validated800_90B)
+        );
+    }
+
+    #[test]
+    fn deterministic_must_be_provided() {
+        let requirements = SchemaBuilder::new().security_bits(128).deterministic().build();
+        let siv = SchemaBuilder::new()
+            .security_bits(128)
+            .deterministic()
+            .nonce_misuse_resistance()
+            .build();
+        let gcm_siv = SchemaBuilder::new().security_bits(128).nonce_misuse_resistance().build();
+
+        assert!(siv.satisfies(&requirements));
+        assert!(!gcm_siv.satisfies(&requirements));
+        assert_eq!(gcm_siv.delta(&requirements).runes(), [Rune::Deterministic]);
+    }
+
+    #[test]
+    fn memory_hardness_compares_memory_and_iterations() {
+        let costs = |memory_kib, iterations, parallelism| {
+            SchemaBuilder::new()
+                .memory_hardness(CostParameters { memory_kib, iterations, parallelism })
+                .build()
+        };
+        let requirements = costs(19 * 1024, 2, 1);
+
+        assert!(costs(19 * 1024, 2, 1).satisfies(&requirements));
+        assert!(costs(64 * 1024, 3, 4).satisfies(&requirements));
+        assert!(costs(19 * 1024, 2, 8).satisfies(&requirements));
+        assert!(!costs(64 * 1024, 1, 1).satisfies(&requirements));
+        assert!(!costs(12 * 1024, 3, 1).satisfies(&requirements));
+        assert_eq!(
+            costs(64 * 1024, 1, 4).merge(&costs(19 * 1024, 2, 1)).unwrap(),
+            costs(64 * 1024, 2, 4)
+        );
+    }
+
+    #[test]
+    fn min_memory_kib_constrains_only_memory() {
+        let requirements = SchemaBuilder::new().min_memory_kib(19 * 1024).build();
+        let costs = |memory_kib, iterations, parallelism| {
+            SchemaBuilder::new()
+                .memory_hardness(CostParameters { memory_kib, iterations, parallelism })
+                .build()
+        };
+
+        assert_eq!(requirements, costs(19 * 1024, 1, 1));
+        assert!(costs(19 * 1024, 1, 1).satisfies(&requirements));
+        assert!(costs(64 * 1024, 10, 4).satisfies(&requirements));
+        assert!(!costs(16 * 1024, 10, 4).satisfies(&requirements));
+    }
+
+    #[test]
+    fn contributory_key_agreement_must_be_provided() {
+        let requirements =
+            SchemaBuilder::new().security_bits(128).contributory_key_agreement().build();
+        let checked = SchemaBuilder::new().security_bits(128).contributory_key_agreement().build();
+        let unchecked = SchemaBuilder::new().security_bits(128).build();
+
+        assert!(checked.satisfies(&requirements));
+        assert!(!unchecked.satisfies(&requirements));
+        assert_eq!(unchecked.delta(&requirements).runes(), [Rune::ContributoryKeyAgreement]);
+    }
+
+    #[test]
+    fn multi_recipient_is_met_by_a_higher_maximum() {
+        let recipients = |max| SchemaBuilder::new().multi_recipient(max).build();
+        let requirements = recipients(8);
+
+        assert!(recipients(8).satisfies(&requirements));
+        assert!(recipients(64).satisfies(&requirements));
+        assert!(!recipients(4).satisfies(&requirements));
+        assert!(!SchemaBuilder::new().build().satisfies(&requirements));
+        assert_eq!(recipients(4).merge(&recipients(8)).unwrap(), recipients(8));
+    }
+
+    #[test]
+    fn threshold_needs_as_many_parties_and_no_larger_quorum() {
+        let threshold = |t, n| SchemaBuilder::new().threshold(t, n).unwrap().build();
+        let requirements = threshold(2, 3);
+
+        assert!(threshold(2, 3).satisfies(&requirements));
+        assert!(threshold(2, 5).satisfies(&requirements));
+        assert!(threshold(1, 3).satisfies(&requirements));
+        assert!(!threshold(3, 3).satisfies(&requirements));
+        assert!(!threshold(2, 2).satisfies(&requirements));
+        assert_eq!(threshold(3, 5).merge(&threshold(2, 3)).unwrap(), threshold(2, 5));
+        assert!(matches!(SchemaBuilder::new().threshold(0, 3), Err(Error::InvalidParameter(_))));
+        assert!(matches!(SchemaBuilder::new().threshold(4, 3), Err(Error::InvalidParameter(_))));
+    }
 }
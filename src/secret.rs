@@ -0,0 +1,66 @@
+//! A buffer for secret bytes, such as shared secrets and derived key material, that is wiped
+//! from memory when dropped.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use zeroize::Zeroize;
+
+/// Secret bytes that are overwritten, with volatile writes the optimizer can't elide, when the
+/// `Secret` is dropped.
+///
+/// `Secret` deliberately doesn't implement [`Clone`], so that every copy of the bytes is explicit,
+/// and its [`fmt::Debug`] output omits them, so they can't leak into logs.
+pub struct Secret(Vec<u8>);
+
+impl Secret {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<u8>> for Secret {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret([redacted])")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_output_redacts_the_bytes() {
+        let secret = Secret::from(vec![0x5e, 0xc2, 0xe7]);
+
+        assert_eq!(secret.as_slice(), [0x5e, 0xc2, 0xe7]);
+        assert_eq!(secret.len(), 3);
+        assert!(!secret.is_empty());
+        assert!(Secret::new(Vec::new()).is_empty());
+        assert_eq!(format!("{:?}", secret), "Secret([redacted])");
+    }
+}
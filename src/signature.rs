@@ -1 +1,193 @@
+use alloc::vec::Vec;
 
+use crate::{CryptographicPrimitive, error::Result};
+
+/// The signing half of a digital signature scheme, such as Ed25519 or ECDSA, holding the private
+/// key.
+///
+/// Messages may be signed in one shot with [`Signer::sign`], or in chunks with a
+/// [`SigningContext`] obtained from [`Signer::start`], so that large messages need not be held in
+/// memory.  Both produce the same signature for the same message.
+pub trait Signer: CryptographicPrimitive {
+    type Context: SigningContext;
+
+    /// Returns the length of the signatures produced, in bytes, or the maximum length for schemes
+    /// with variable-length encodings, such as DER-encoded ECDSA.  Callers may use it to size
+    /// buffers, but must not assume every signature is exactly this long.
+    fn signature_size(&self) -> usize;
+
+    /// Begins signing a message that will be supplied in chunks.
+    fn start(&self) -> Result<Self::Context>;
+
+    /// Signs `message`, returning the encoded signature.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let mut context = self.start()?;
+        context.update(message)?;
+        context.finish()
+    }
+}
+
+/// A message being signed in chunks.  See [`Signer::start`].
+///
+/// Schemes that must see the whole message before signing, such as pure Ed25519, which hashes
+/// the message twice, have to buffer it, so constrained devices should prefer schemes or variants
+/// that hash the message once, such as ECDSA or Ed25519ph.
+pub trait SigningContext {
+    /// Appends `chunk` to the message.
+    fn update(&mut self, chunk: &[u8]) -> Result<()>;
+
+    /// Signs the message, returning the encoded signature.
+    fn finish(self) -> Result<Vec<u8>>;
+}
+
+/// The verifying half of a digital signature scheme, holding the public key.
+///
+/// As with [`Signer`], messages may be verified in one shot or in chunks.
+pub trait Verifier: CryptographicPrimitive {
+    type Context: VerificationContext;
+
+    /// Returns the length of the signatures accepted, in bytes, or the maximum length for schemes
+    /// with variable-length encodings.
+    fn signature_size(&self) -> usize;
+
+    /// Begins verifying a message that will be supplied in chunks.
+    fn start(&self) -> Result<Self::Context>;
+
+    /// Checks that `signature` is a valid signature of `message`.  Returns `Ok(false)` for a
+    /// well-formed signature that does not verify, and fails with
+    /// [`crate::error::Error::InvalidParameter`] if `signature` is malformed, e.g. of the wrong
+    /// length or not a valid encoding.  Callers that don't need to tell the cases apart should
+    /// treat both as a verification failure.
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        let mut context = self.start()?;
+        context.update(message)?;
+        context.finish(signature)
+    }
+}
+
+/// A message being verified in chunks.  See [`Verifier::start`].
+pub trait VerificationContext {
+    /// Appends `chunk` to the message.
+    fn update(&mut self, chunk: &[u8]) -> Result<()>;
+
+    /// Checks that `signature` is a valid signature of the message, with the same results as
+    /// [`Verifier::verify`].
+    fn finish(self, signature: &[u8]) -> Result<bool>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Error,
+        runes::{Schema, SchemaBuilder},
+    };
+
+    /// A toy signature scheme whose "signature" is a non-cryptographic FNV-1a hash of a key
+    /// shared by the signer and verifier followed by the message.
+    struct ToyKey(u8);
+
+    struct ToyContext(u64);
+
+    /// A [`ToyContext`] used for verification, so that calls on it are not ambiguous between
+    /// [`SigningContext`] and [`VerificationContext`].
+    struct ToyVerification(ToyContext);
+
+    impl ToyContext {
+        fn new(key: &ToyKey) -> Self {
+            let mut context = Self(0xcbf29ce484222325);
+            context.absorb(&[key.0]);
+            context
+        }
+
+        fn absorb(&mut self, chunk: &[u8]) {
+            for byte in chunk {
+                self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+
+    impl CryptographicPrimitive for ToyKey {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new().build()
+        }
+    }
+
+    impl Signer for ToyKey {
+        type Context = ToyContext;
+
+        fn signature_size(&self) -> usize {
+            8
+        }
+
+        fn start(&self) -> Result<ToyContext> {
+            Ok(ToyContext::new(self))
+        }
+    }
+
+    impl SigningContext for ToyContext {
+        fn update(&mut self, chunk: &[u8]) -> Result<()> {
+            self.absorb(chunk);
+            Ok(())
+        }
+
+        fn finish(self) -> Result<Vec<u8>> {
+            Ok(self.0.to_be_bytes().to_vec())
+        }
+    }
+
+    impl Verifier for ToyKey {
+        type Context = ToyVerification;
+
+        fn signature_size(&self) -> usize {
+            8
+        }
+
+        fn start(&self) -> Result<ToyVerification> {
+            Ok(ToyVerification(ToyContext::new(self)))
+        }
+    }
+
+    impl VerificationContext for ToyVerification {
+        fn update(&mut self, chunk: &[u8]) -> Result<()> {
+            self.0.absorb(chunk);
+            Ok(())
+        }
+
+        fn finish(self, signature: &[u8]) -> Result<bool> {
+            let signature: [u8; 8] = signature.try_into().map_err(|_| {
+                Error::InvalidParameter(format!("{} byte signature", signature.len()))
+            })?;
+            Ok(u64::from_be_bytes(signature) == self.0.0)
+        }
+    }
+
+    #[test]
+    fn invalid_signatures_are_rejected() {
+        let key = ToyKey(7);
+        let signature = key.sign(b"message").unwrap();
+
+        assert_eq!(signature.len(), Signer::signature_size(&key));
+        assert!(key.verify(b"message", &signature).unwrap());
+        assert!(!key.verify(b"massage", &signature).unwrap());
+        assert!(!ToyKey(8).verify(b"message", &signature).unwrap());
+        assert!(matches!(key.verify(b"message", &signature[..7]), Err(Error::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn chunked_signing_matches_one_shot_signing() {
+        let key = ToyKey(7);
+        let signature = key.sign(b"a longer message").unwrap();
+
+        let mut signing = Signer::start(&key).unwrap();
+        for chunk in [&b"a lon"[..], b"", b"ger message"] {
+            signing.update(chunk).unwrap();
+        }
+        assert_eq!(signing.finish().unwrap(), signature);
+
+        let mut verifying = Verifier::start(&key).unwrap();
+        verifying.update(b"a longer ").unwrap();
+        verifying.update(b"message").unwrap();
+        assert!(verifying.finish(&signature).unwrap());
+    }
+}
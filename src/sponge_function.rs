@@ -1 +1,428 @@
+use alloc::vec::Vec;
 
+use crate::{
+    CryptographicPrimitive,
+    error::{Error, Result},
+    hash_function::ExtendableOutputFunction,
+    message_authentication_code::Mac,
+    runes::Schema,
+};
+
+/// A sponge function, such as Keccak-f\[1600\], which underlies SHA-3, SHAKE and duplex AEADs.
+/// The sponge's state of `rate() + capacity()` bytes is divided into an outer part of `rate()`
+/// bytes, through which data is absorbed and output squeezed, and an inner part of `capacity()`
+/// bytes, which is never directly input or output.
+///
+/// Against generic attacks, a sponge provides security of about `capacity() * 8 / 2` bits,
+/// because an attacker must find a collision in the inner state, which the rate never exposes.
+/// In keyed duplex mode the bound is similar but degrades with the amount of data processed
+/// under a key, so `security_properties()` should report [`crate::runes::Rune::SecurityBits`] no
+/// higher than half the capacity in bits, and limits reflecting the data-dependent degradation.
+///
+/// Any sponge can serve as an [`ExtendableOutputFunction`] by wrapping it in a [`SpongeXof`].
+pub trait Sponge: CryptographicPrimitive {
+    /// Returns the rate, in bytes.
+    fn rate(&self) -> usize;
+
+    /// Returns the capacity, in bytes.
+    fn capacity(&self) -> usize;
+
+    /// Returns the padding applied to the input when the absorb phase ends.
+    fn padding(&self) -> SpongePadding;
+
+    /// Absorbs `data` into the outer part of the state, applying the permutation after each
+    /// `rate()` bytes.  Fails with [`crate::error::Error::InternalError`] once output has been
+    /// squeezed.
+    fn absorb(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Fills `out` with output read from the outer part of the state, applying the permutation
+    /// after each `rate()` bytes.  The first call pads the input and ends the absorb phase.
+    fn squeeze(&mut self, out: &mut [u8]);
+}
+
+/// A [`Sponge`] that has absorbed a key, and so can serve as a [`Mac`] by wrapping it in a
+/// [`SpongeMac`], as in KMAC: the message is absorbed and the tag squeezed.  The key must have
+/// been absorbed with padding to a full `rate()` block, so that it cannot run into the message.
+///
+/// Keyed sponges can also provide authenticated encryption in duplex mode, with
+/// [`KeyedSponge::duplex_seal`] and [`KeyedSponge::duplex_open`].  Unkeyed sponges cannot, since
+/// anyone could recompute the keystream and the tag.
+pub trait KeyedSponge: Sponge + Sized {
+    /// Returns the length of the tag to squeeze, in bytes.
+    fn tag_size(&self) -> usize;
+
+    /// Authenticated encryption in duplex mode, combining what would otherwise be separate hash
+    /// and cipher passes: absorbs `aad`, then encrypts `plaintext` a rate-sized block at a time,
+    /// each block of ciphertext being the plaintext XORed with the outer state, which then
+    /// replaces it.  Finally squeezes an authentication tag.  Returns the ciphertext and the tag.
+    ///
+    /// The sponge must already have been given a unique nonce, and is left in a state that must
+    /// not be reused for another message.  Duplexing needs access to the outer state between
+    /// permutations, which [`Sponge::absorb`] and [`Sponge::squeeze`] don't give, so the default
+    /// implementation returns [`Error::Unsupported`].
+    fn duplex_seal(&mut self, _aad: &[u8], _plaintext: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        Err(Error::Unsupported("Duplex mode is not supported".into()))
+    }
+
+    /// Reverses [`KeyedSponge::duplex_seal`], given a sponge keyed with the same key and nonce.
+    /// Fails with [`Error::AuthenticationFailed`] if the squeezed tag does not match `tag`,
+    /// compared in constant time with [`crate::ct::ct_eq`], in which case no plaintext is
+    /// released.  The default implementation returns [`Error::Unsupported`].
+    fn duplex_open(&mut self, _aad: &[u8], _ciphertext: &[u8], _tag: &[u8]) -> Result<Vec<u8>> {
+        Err(Error::Unsupported("Duplex mode is not supported".into()))
+    }
+}
+
+/// The padding a [`Sponge`] applies to its input before squeezing.  For Keccak-based sponges this
+/// also selects the domain separation bits that keep the functions built on the same permutation
+/// independent of each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpongePadding {
+    /// Multi-rate padding, pad10*1, with no domain separation bits, as in the original Keccak
+    /// submission.
+    Pad10Star1,
+
+    /// pad10*1 preceded by the bits `01`, as in SHA3-224 through SHA3-512.
+    Sha3,
+
+    /// pad10*1 preceded by the bits `1111`, as in SHAKE128 and SHAKE256.
+    Shake,
+
+    /// pad10*1 preceded by the bits `00`, as in cSHAKE and KMAC.
+    CShake,
+}
+
+/// An [`ExtendableOutputFunction`] built on a [`Sponge`], as SHAKE128 and SHAKE256 are built on
+/// Keccak-f\[1600\]: input is absorbed with [`Sponge::absorb`] and output squeezed with
+/// [`Sponge::squeeze`].  Its security properties are those of the sponge.
+pub struct SpongeXof<S: Sponge> {
+    sponge: S,
+}
+
+impl<S: Sponge> SpongeXof<S> {
+    pub fn new(sponge: S) -> Self {
+        Self { sponge }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.sponge
+    }
+}
+
+impl<S: Sponge> CryptographicPrimitive for SpongeXof<S> {
+    fn security_properties(&self) -> Schema {
+        self.sponge.security_properties()
+    }
+}
+
+impl<S: Sponge> ExtendableOutputFunction for SpongeXof<S> {
+    fn update(&mut self, data: &[u8]) -> Result<()> {
+        self.sponge.absorb(data)
+    }
+
+    fn squeeze(&mut self, out: &mut [u8]) {
+        self.sponge.squeeze(out)
+    }
+}
+
+/// A [`Mac`] built on a [`KeyedSponge`], as KMAC is built on cSHAKE: the message is absorbed with
+/// [`Sponge::absorb`] and a tag of [`KeyedSponge::tag_size`] bytes squeezed with
+/// [`Sponge::squeeze`].  Its security properties are those of the sponge.
+pub struct SpongeMac<S: KeyedSponge> {
+    sponge: S,
+}
+
+impl<S: KeyedSponge> SpongeMac<S> {
+    pub fn new(sponge: S) -> Self {
+        Self { sponge }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.sponge
+    }
+}
+
+impl<S: KeyedSponge> CryptographicPrimitive for SpongeMac<S> {
+    fn security_properties(&self) -> Schema {
+        self.sponge.security_properties()
+    }
+}
+
+impl<S: KeyedSponge> Mac for SpongeMac<S> {
+    fn tag_size(&self) -> usize {
+        self.sponge.tag_size()
+    }
+
+    fn update(&mut self, data: &[u8]) -> Result<()> {
+        self.sponge.absorb(data)
+    }
+
+    fn finalize(mut self) -> Result<Vec<u8>> {
+        let mut tag = vec![0; self.sponge.tag_size()];
+        self.sponge.squeeze(&mut tag);
+        Ok(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ct::ct_eq, hash_function::tests::check_xof, runes::SchemaBuilder};
+
+    const RATE: usize = 8;
+
+    /// A toy sponge with an 8-byte rate and an 8-byte capacity, whose permutation mixes the state
+    /// just enough for every output byte to depend on every input byte.
+    #[derive(Default)]
+    struct ToySponge {
+        state: [u8; 16],
+        position: usize,
+        squeezing: bool,
+    }
+
+    impl ToySponge {
+        fn permute(&mut self) {
+            for round in 0..4 {
+                for i in 0..self.state.len() {
+                    let j = (i + 1) % self.state.len();
+                    self.state[j] =
+                        self.state[j].wrapping_add(self.state[i].rotate_left(3) ^ round);
+                }
+            }
+            self.position = 0;
+        }
+    }
+
+    impl CryptographicPrimitive for ToySponge {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new().security_bits(32).build()
+        }
+    }
+
+    impl Sponge for ToySponge {
+        fn rate(&self) -> usize {
+            RATE
+        }
+
+        fn capacity(&self) -> usize {
+            self.state.len() - RATE
+        }
+
+        fn padding(&self) -> SpongePadding {
+            SpongePadding::Pad10Star1
+        }
+
+        fn absorb(&mut self, data: &[u8]) -> Result<()> {
+            if self.squeezing {
+                return Err(Error::InternalError("Cannot absorb after squeezing".into()));
+            }
+            for byte in data {
+                self.state[self.position] ^= byte;
+                self.position += 1;
+                if self.position == RATE {
+                    self.permute();
+                }
+            }
+            Ok(())
+        }
+
+        fn squeeze(&mut self, out: &mut [u8]) {
+            if !self.squeezing {
+                self.state[self.position] ^= 0x01;
+                self.state[RATE - 1] ^= 0x80;
+                self.permute();
+                self.squeezing = true;
+            }
+            for byte in out {
+                if self.position == RATE {
+                    self.permute();
+                }
+                *byte = self.state[self.position];
+                self.position += 1;
+            }
+        }
+    }
+
+    /// A [`ToySponge`] that has absorbed a one-block key, and implements duplex mode directly on
+    /// its state.
+    struct ToyKeyedSponge(ToySponge);
+
+    impl ToyKeyedSponge {
+        fn new(key: [u8; RATE]) -> Self {
+            let mut sponge = ToySponge::default();
+            sponge.absorb(&key).unwrap();
+            Self(sponge)
+        }
+
+        /// Absorbs `aad` and ends it with a padding byte, then encrypts or decrypts `input`,
+        /// overwriting the outer state with the ciphertext, and squeezes a tag.  Returns the
+        /// output and the tag.
+        fn duplex(
+            &mut self,
+            aad: &[u8],
+            input: &[u8],
+            decrypting: bool,
+        ) -> Result<(Vec<u8>, Vec<u8>)> {
+            self.0.absorb(aad)?;
+            self.0.state[self.0.position] ^= 0x01;
+            self.0.permute();
+
+            let mut output = Vec::with_capacity(input.len());
+            for byte in input {
+                let outer = &mut self.0.state[self.0.position];
+                output.push(byte ^ *outer);
+                *outer = if decrypting { *byte } else { byte ^ *outer };
+                self.0.position += 1;
+                if self.0.position == RATE {
+                    self.0.permute();
+                }
+            }
+
+            let mut tag = vec![0; self.tag_size()];
+            self.0.squeeze(&mut tag);
+            Ok((output, tag))
+        }
+    }
+
+    impl CryptographicPrimitive for ToyKeyedSponge {
+        fn security_properties(&self) -> Schema {
+            self.0.security_properties()
+        }
+    }
+
+    impl Sponge for ToyKeyedSponge {
+        fn rate(&self) -> usize {
+            self.0.rate()
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.capacity()
+        }
+
+        fn padding(&self) -> SpongePadding {
+            self.0.padding()
+        }
+
+        fn absorb(&mut self, data: &[u8]) -> Result<()> {
+            self.0.absorb(data)
+        }
+
+        fn squeeze(&mut self, out: &mut [u8]) {
+            self.0.squeeze(out)
+        }
+    }
+
+    impl KeyedSponge for ToyKeyedSponge {
+        fn tag_size(&self) -> usize {
+            16
+        }
+
+        fn duplex_seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+            self.duplex(aad, plaintext, false)
+        }
+
+        fn duplex_open(&mut self, aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>> {
+            let (plaintext, expected) = self.duplex(aad, ciphertext, true)?;
+            if !ct_eq(&expected, tag) {
+                return Err(Error::AuthenticationFailed);
+            }
+            Ok(plaintext)
+        }
+    }
+
+    #[test]
+    fn duplex_open_reverses_duplex_seal() {
+        let plaintext = b"a plaintext of more than one block";
+        let (ciphertext, tag) =
+            ToyKeyedSponge::new(*b"toy key!").duplex_seal(b"aad", plaintext).unwrap();
+
+        assert_eq!(ciphertext.len(), plaintext.len());
+        assert_ne!(ciphertext, plaintext);
+        assert_eq!(tag.len(), 16);
+        let opened =
+            ToyKeyedSponge::new(*b"toy key!").duplex_open(b"aad", &ciphertext, &tag).unwrap();
+        assert_eq!(opened, plaintext);
+
+        let mut sealed = ToyKeyedSponge::new(*b"toy key!");
+        sealed.duplex_seal(b"aad", plaintext).unwrap();
+        assert!(matches!(sealed.duplex_seal(b"aad", plaintext), Err(Error::InternalError(_))));
+    }
+
+    #[test]
+    fn duplex_open_rejects_tampering() {
+        let (ciphertext, tag) =
+            ToyKeyedSponge::new(*b"toy key!").duplex_seal(b"aad", b"plaintext").unwrap();
+        let open = |aad: &[u8], ciphertext: &[u8], tag: &[u8]| {
+            ToyKeyedSponge::new(*b"toy key!").duplex_open(aad, ciphertext, tag)
+        };
+
+        let mut tampered_tag = tag.clone();
+        tampered_tag[0] ^= 0x01;
+        assert!(matches!(
+            open(b"aad", &ciphertext, &tampered_tag),
+            Err(Error::AuthenticationFailed)
+        ));
+        let mut tampered_ciphertext = ciphertext.clone();
+        tampered_ciphertext[3] ^= 0x80;
+        assert!(matches!(
+            open(b"aad", &tampered_ciphertext, &tag),
+            Err(Error::AuthenticationFailed)
+        ));
+        assert!(matches!(open(b"aaD", &ciphertext, &tag), Err(Error::AuthenticationFailed)));
+        assert!(matches!(
+            ToyKeyedSponge::new(*b"toy key?").duplex_open(b"aad", &ciphertext, &tag),
+            Err(Error::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn sponge_xof_squeezes_the_sponge() {
+        check_xof(|| SpongeXof::new(ToySponge::default()));
+
+        let mut xof = SpongeXof::new(ToySponge::default());
+        xof.update(b"input").unwrap();
+        let mut output = [0; 12];
+        xof.squeeze(&mut output);
+        assert_eq!(xof.security_properties(), ToySponge::default().security_properties());
+
+        let mut sponge = ToySponge::default();
+        sponge.absorb(b"input").unwrap();
+        let mut expected = [0; 12];
+        sponge.squeeze(&mut expected);
+        assert_eq!(output, expected);
+        let mut next = [0; 4];
+        xof.into_inner().squeeze(&mut next);
+        sponge.squeeze(&mut expected[..4]);
+        assert_eq!(next, expected[..4]);
+    }
+
+    #[test]
+    fn sponge_mac_squeezes_a_tag_from_the_keyed_sponge() {
+        let tag = |key, message: &[u8]| {
+            let mut mac = SpongeMac::new(ToyKeyedSponge::new(key));
+            mac.update(message).unwrap();
+            mac.finalize().unwrap()
+        };
+
+        let expected = tag(*b"toy key!", b"message");
+        let mut sponge = ToyKeyedSponge::new(*b"toy key!");
+        sponge.absorb(b"message").unwrap();
+        let mut squeezed = [0; 16];
+        sponge.squeeze(&mut squeezed);
+        assert_eq!(expected, squeezed);
+        assert_ne!(tag(*b"toy key?", b"message"), expected);
+
+        let mut mac = SpongeMac::new(ToyKeyedSponge::new(*b"toy key!"));
+        assert_eq!(mac.tag_size(), 16);
+        assert_eq!(mac.security_properties(), ToySponge::default().security_properties());
+        mac.update(b"mess").unwrap();
+        mac.update(b"age").unwrap();
+        assert!(mac.verify(&expected).unwrap());
+        let mut mac = SpongeMac::new(ToyKeyedSponge::new(*b"toy key!"));
+        mac.update(b"massage").unwrap();
+        assert!(!mac.verify(&expected).unwrap());
+        assert!(
+            SpongeMac::new(ToyKeyedSponge::new(*b"toy key!")).into_inner().absorb(b"x").is_ok()
+        );
+    }
+}
@@ -1 +1,102 @@
+use crate::{CryptographicPrimitive, error::Result};
 
+/// A stream cipher, such as ChaCha20 or a block cipher in CTR mode, keyed and given a nonce,
+/// which encrypts by XORing data with a keystream.  Encryption and decryption are the same
+/// operation.
+///
+/// Reusing a nonce with the same key produces the same keystream, and XORing two ciphertexts
+/// encrypted with it cancels the keystream and reveals the XOR of the plaintexts, which is
+/// usually enough to recover both.  Nonces must therefore never repeat under a key, and the
+/// keystream must not wrap around, so `security_properties()` should report the keystream length
+/// as [`crate::runes::Rune::TotalDataLimit`], e.g. 2³⁸ bytes for ChaCha20 with a 32-bit counter.
+pub trait StreamCipher: CryptographicPrimitive {
+    /// Returns the length of the nonce, in bytes.
+    fn nonce_size(&self) -> usize;
+
+    /// XORs `data` in place with the next `data.len()` bytes of keystream.
+    fn apply_keystream(&mut self, data: &mut [u8]);
+
+    /// Moves to the start of keystream block `block_offset`, for random access, so that the next
+    /// call to [`StreamCipher::apply_keystream`] uses the keystream from there.  Fails with
+    /// [`crate::error::Error::TotalDataTooLong`] if the offset is beyond the end of the
+    /// keystream.
+    fn seek(&mut self, block_offset: u64) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::Error,
+        runes::{Schema, SchemaBuilder},
+    };
+
+    const BLOCK: usize = 8;
+    const BLOCKS: u64 = 4;
+
+    /// A toy stream cipher with a keystream of four 8-byte blocks, each a non-cryptographic
+    /// FNV-1a mix of the key, nonce and block number.
+    struct ToyStreamCipher {
+        key: u64,
+        nonce: u64,
+        position: u64,
+    }
+
+    impl CryptographicPrimitive for ToyStreamCipher {
+        fn security_properties(&self) -> Schema {
+            SchemaBuilder::new()
+                .total_data_limit(u128::from(BLOCKS) * BLOCK as u128)
+                .unwrap()
+                .build()
+        }
+    }
+
+    impl StreamCipher for ToyStreamCipher {
+        fn nonce_size(&self) -> usize {
+            8
+        }
+
+        fn apply_keystream(&mut self, data: &mut [u8]) {
+            for byte in data {
+                let block = self.position / BLOCK as u64;
+                let keystream = (self.key ^ self.nonce.rotate_left(32) ^ block)
+                    .wrapping_mul(0x100000001b3)
+                    .to_be_bytes();
+                *byte ^= keystream[(self.position % BLOCK as u64) as usize];
+                self.position += 1;
+            }
+        }
+
+        fn seek(&mut self, block_offset: u64) -> Result<()> {
+            if block_offset >= BLOCKS {
+                return Err(Error::TotalDataTooLong(format!("Block {}", block_offset)));
+            }
+            self.position = block_offset * BLOCK as u64;
+            Ok(())
+        }
+    }
+
+    fn cipher() -> ToyStreamCipher {
+        ToyStreamCipher { key: 0x0123456789abcdef, nonce: 7, position: 0 }
+    }
+
+    #[test]
+    fn seek_gives_random_access_to_the_keystream() {
+        let plaintext = [0x55; 3 * BLOCK];
+        let mut ciphertext = plaintext;
+        cipher().apply_keystream(&mut ciphertext);
+        assert_ne!(ciphertext, plaintext);
+
+        let mut decrypted = ciphertext;
+        cipher().apply_keystream(&mut decrypted);
+        assert_eq!(decrypted, plaintext);
+
+        let mut middle = [0x55; BLOCK];
+        let mut seeking = cipher();
+        seeking.seek(1).unwrap();
+        seeking.apply_keystream(&mut middle);
+        assert_eq!(middle, ciphertext[BLOCK..2 * BLOCK]);
+
+        assert!(matches!(seeking.seek(BLOCKS), Err(Error::TotalDataTooLong(_))));
+    }
+}
@@ -0,0 +1,383 @@
+//! Test doubles for code written against the [`Provider`] and [`BindRune`] traits.
+//!
+//! [`MockProvider`] records every call made to it and lets tests script the outcome of forging:
+//! the schema reported by forged [`BindRune`]s can be fixed or computed from the request, and
+//! errors can be injected to exercise failure paths.
+//!
+//! The mocks perform no cryptography at all.  Sealing and unsealing return the input data
+//! unchanged, so they must never be used outside of tests.  This module is only available with the
+//! `testing` feature, and in the crate's own tests.
+
+use alloc::{
+    boxed::Box,
+    collections::{btree_map::BTreeMap, vec_deque::VecDeque},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cell::RefCell;
+
+use crate::{
+    construction::ConstructionParams,
+    error::{Error, Result},
+    provider::{BindRune, CounterState, Operation, OperationResult, Provider, VariationParam},
+    runes::{KeyUsage, Schema},
+};
+
+/// A call made to a [`MockProvider`], as recorded in its call log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderCall {
+    Forge { label: String, desired_properties: Schema },
+    ForgeShareable { label: String, uuid: u128, desired_properties: Schema },
+    Retrieve { label: String },
+    ImportRaw { label: String, construction: ConstructionParams, requirements: Schema },
+    ForgeAndSeal { label: String, requirements: Schema },
+    RestoreCounters { label: String, state: CounterState },
+}
+
+type ForgeHandler = Box<dyn Fn(&str, &Schema) -> Result<Schema>>;
+
+/// A [`Provider`] whose behavior is scripted by the test.
+///
+/// By default, forging succeeds and the resulting [`MockBindRune`] reports exactly the requested
+/// schema.  Importing raw key material is treated as forging with the import's requirements, and
+/// restoring counters as retrieving.  Use [`MockProvider::with_schema`] or
+/// [`MockProvider::on_forge`] to change that, and [`MockProvider::fail_next`] to make calls fail.
+pub struct MockProvider {
+    forge_handler: ForgeHandler,
+    injected_errors: RefCell<VecDeque<Error>>,
+    forged: RefCell<BTreeMap<String, Schema>>,
+    calls: RefCell<Vec<ProviderCall>>,
+}
+
+impl Default for MockProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockProvider {
+    pub fn new() -> Self {
+        Self {
+            forge_handler: Box::new(|_, desired_properties| Ok(desired_properties.clone())),
+            injected_errors: RefCell::new(VecDeque::new()),
+            forged: RefCell::new(BTreeMap::new()),
+            calls: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Makes every forged [`BindRune`] report `schema`, regardless of the request.
+    pub fn with_schema(self, schema: Schema) -> Self {
+        self.on_forge(move |_, _| Ok(schema.clone()))
+    }
+
+    /// Computes the outcome of each forge from the label and requested schema.  The handler
+    /// returns the schema the forged [`BindRune`] reports, or the error to return to the caller.
+    pub fn on_forge(mut self, handler: impl Fn(&str, &Schema) -> Result<Schema> + 'static) -> Self {
+        self.forge_handler = Box::new(handler);
+        self
+    }
+
+    /// Makes the next call to any [`Provider`] method fail with `error`.  Injected errors are
+    /// queued, so calling this repeatedly fails that many subsequent calls, in order.
+    pub fn fail_next(&self, error: Error) {
+        self.injected_errors.borrow_mut().push_back(error);
+    }
+
+    /// Returns all calls made to the provider, oldest first.
+    pub fn calls(&self) -> Vec<ProviderCall> {
+        self.calls.borrow().clone()
+    }
+
+    /// Returns the number of forge requests made, whether or not they succeeded.
+    pub fn forge_count(&self) -> usize {
+        self.calls
+            .borrow()
+            .iter()
+            .filter(|call| {
+                matches!(
+                    call,
+                    ProviderCall::Forge { .. }
+                        | ProviderCall::ForgeShareable { .. }
+                        | ProviderCall::ForgeAndSeal { .. }
+                )
+            })
+            .count()
+    }
+
+    /// Panics unless `expected` was among the calls made to the provider.
+    pub fn assert_called(&self, expected: &ProviderCall) {
+        let calls = self.calls.borrow();
+        assert!(calls.contains(expected), "expected call {:?} not found in {:?}", expected, calls);
+    }
+
+    /// Panics if any calls were made to the provider.
+    pub fn assert_not_called(&self) {
+        let calls = self.calls.borrow();
+        assert!(calls.is_empty(), "expected no calls, found {:?}", calls);
+    }
+
+    fn record(&self, call: ProviderCall) -> Result<()> {
+        self.calls.borrow_mut().push(call);
+        match self.injected_errors.borrow_mut().pop_front() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn forge_bind_rune(
+        &self,
+        label: &str,
+        desired_properties: &Schema,
+    ) -> Result<Box<dyn BindRune>> {
+        let schema = (self.forge_handler)(label, desired_properties)?;
+        self.forged.borrow_mut().insert(label.to_string(), schema.clone());
+        Ok(Box::new(MockBindRune::new(schema)))
+    }
+
+    fn retrieve_bind_rune(&self, label: &str) -> Result<Box<dyn BindRune>> {
+        match self.forged.borrow().get(label) {
+            Some(schema) => Ok(Box::new(MockBindRune::new(schema.clone()))),
+            None => Err(Error::UnknownLabel),
+        }
+    }
+}
+
+impl Provider for MockProvider {
+    fn forge(&self, label: &str, desired_properties: Schema) -> Result<Box<dyn BindRune>> {
+        self.record(ProviderCall::Forge {
+            label: label.to_string(),
+            desired_properties: desired_properties.clone(),
+        })?;
+        self.forge_bind_rune(label, &desired_properties)
+    }
+
+    fn forge_shareable(
+        &self,
+        label: &str,
+        uuid: u128,
+        desired_properties: Schema,
+    ) -> Result<Box<dyn BindRune>> {
+        self.record(ProviderCall::ForgeShareable {
+            label: label.to_string(),
+            uuid,
+            desired_properties: desired_properties.clone(),
+        })?;
+        self.forge_bind_rune(label, &desired_properties)
+    }
+
+    fn retrieve(&self, label: &str) -> Result<Box<dyn BindRune>> {
+        self.record(ProviderCall::Retrieve { label: label.to_string() })?;
+        self.retrieve_bind_rune(label)
+    }
+
+    fn import_raw(
+        &self,
+        label: &str,
+        _key_material: &[u8],
+        construction: &ConstructionParams,
+        requirements: &Schema,
+    ) -> Result<Box<dyn BindRune>> {
+        self.record(ProviderCall::ImportRaw {
+            label: label.to_string(),
+            construction: construction.clone(),
+            requirements: requirements.clone(),
+        })?;
+        self.forge_bind_rune(label, requirements)
+    }
+
+    fn forge_and_seal(
+        &self,
+        label: &str,
+        requirements: &Schema,
+        plaintext: &[u8],
+    ) -> Result<(Box<dyn BindRune>, OperationResult)> {
+        self.record(ProviderCall::ForgeAndSeal {
+            label: label.to_string(),
+            requirements: requirements.clone(),
+        })?;
+        let bind_rune = self.forge_bind_rune(label, requirements)?;
+        let operation = bind_rune.seal()?;
+        operation.finish_aad()?;
+        let result = operation.finish(plaintext)?;
+        Ok((bind_rune, result))
+    }
+
+    fn restore_counters(&self, label: &str, state: CounterState) -> Result<Box<dyn BindRune>> {
+        self.record(ProviderCall::RestoreCounters { label: label.to_string(), state })?;
+        self.retrieve_bind_rune(label)
+    }
+}
+
+/// A [`BindRune`] that reports a fixed schema and whose operations pass data through unchanged.
+/// Operations are refused if the schema's [`crate::runes::Rune::KeyUsage`] doesn't permit them.
+#[derive(Debug, Clone)]
+pub struct MockBindRune {
+    schema: Schema,
+}
+
+impl MockBindRune {
+    pub fn new(schema: Schema) -> Self {
+        Self { schema }
+    }
+
+    fn operation(&self) -> Box<dyn Operation> {
+        let counters = self.counter_snapshot();
+        Box::new(MockOperation {
+            message_count_remaining: counters.message_count_remaining,
+            total_data_remaining: counters.total_data_remaining,
+        })
+    }
+}
+
+impl BindRune for MockBindRune {
+    fn seal(&self) -> Result<Box<dyn Operation>> {
+        self.schema.check_usage(KeyUsage::Encrypt)?;
+        Ok(self.operation())
+    }
+
+    fn unseal(&self) -> Result<Box<dyn Operation>> {
+        self.schema.check_usage(KeyUsage::Decrypt)?;
+        Ok(self.operation())
+    }
+
+    fn schema(&self) -> Schema {
+        self.schema.clone()
+    }
+}
+
+/// An [`Operation`] that returns its input unchanged.
+struct MockOperation {
+    message_count_remaining: u128,
+    total_data_remaining: u128,
+}
+
+impl Operation for MockOperation {
+    fn variation_size(&self) -> Option<u16> {
+        None
+    }
+
+    fn set_variation(&self, _variation: VariationParam) -> Result<()> {
+        Ok(())
+    }
+
+    fn update_aad(&self, _aad: &[u8]) -> Result<()> {
+        Ok(())
+    }
+
+    fn finish_aad(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn update(&self, data: &[u8]) -> Result<Vec<u8>> {
+        Ok(data.to_vec())
+    }
+
+    fn finish(&self, data: &[u8]) -> Result<OperationResult> {
+        Ok(OperationResult {
+            data: data.to_vec(),
+            output_parameters: Vec::new(),
+            message_count_remaining: self.message_count_remaining,
+            total_data_remaining: self.total_data_remaining,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        construction::ConstructionIdentifier,
+        runes::{OriginIdentity, SchemaBuilder},
+    };
+
+    #[test]
+    fn records_calls_and_retrieves_forged_schema() {
+        let provider = MockProvider::new();
+        let schema = SchemaBuilder::new().security_bits(128).build();
+
+        provider.forge("key", schema.clone()).unwrap();
+        let retrieved = provider.retrieve("key").unwrap();
+
+        assert_eq!(retrieved.schema(), schema);
+        assert_eq!(
+            provider.calls(),
+            [
+                ProviderCall::Forge { label: "key".into(), desired_properties: schema },
+                ProviderCall::Retrieve { label: "key".into() },
+            ]
+        );
+        assert!(matches!(provider.retrieve("other"), Err(Error::UnknownLabel)));
+    }
+
+    #[test]
+    fn injected_errors_fail_calls_in_order() {
+        let provider = MockProvider::new();
+        let schema = SchemaBuilder::new().build();
+        let params = ConstructionParams::new(ConstructionIdentifier::new("aes-128"));
+        provider.fail_next(Error::UnknownLabel);
+        provider.fail_next(Error::Unsupported("no import".into()));
+
+        assert!(matches!(
+            provider.restore_counters(
+                "key",
+                CounterState { message_count_remaining: 1, total_data_remaining: 1 }
+            ),
+            Err(Error::UnknownLabel)
+        ));
+        assert!(matches!(
+            provider.import_raw("key", &[0; 16], &params, &schema),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(provider.import_raw("key", &[0; 16], &params, &schema).is_ok());
+        provider.assert_called(&ProviderCall::ImportRaw {
+            label: "key".into(),
+            construction: params,
+            requirements: schema,
+        });
+        assert_eq!(provider.calls().len(), 3);
+    }
+
+    #[test]
+    fn forge_and_seal_is_recorded_once() {
+        let provider = MockProvider::new();
+        let schema = SchemaBuilder::new().security_bits(128).build();
+
+        let (_, result) = provider.forge_and_seal("key", &schema, b"data").unwrap();
+
+        assert_eq!(result.data, b"data");
+        assert_eq!(
+            provider.calls(),
+            [ProviderCall::ForgeAndSeal { label: "key".into(), requirements: schema }]
+        );
+        assert_eq!(provider.forge_count(), 1);
+    }
+
+    #[test]
+    fn forge_handler_scripts_schema_and_failures() {
+        let reported = SchemaBuilder::new().security_bits(192).build();
+        let handler_schema = reported.clone();
+        let provider = MockProvider::new().on_forge(move |label, _| match label {
+            "denied" => Err(Error::Unsupported("denied".into())),
+            _ => Ok(handler_schema.clone()),
+        });
+
+        let bind_rune = provider.forge("key", SchemaBuilder::new().build()).unwrap();
+
+        assert_eq!(bind_rune.schema(), reported);
+        assert!(provider.forge("denied", SchemaBuilder::new().build()).is_err());
+        assert_eq!(provider.forge_count(), 2);
+    }
+
+    #[test]
+    fn sign_only_bind_rune_refuses_to_seal() {
+        let provider = MockProvider::new();
+        let signing = SchemaBuilder::for_signing(OriginIdentity, 2035).build();
+
+        let bind_rune = provider.forge("signing-key", signing).unwrap();
+
+        assert!(matches!(bind_rune.seal(), Err(Error::KeyUsageViolation(_))));
+        assert!(matches!(bind_rune.seal_envelope(b"data", b""), Err(Error::KeyUsageViolation(_))));
+        assert!(bind_rune.schema().check_usage(KeyUsage::Sign).is_ok());
+        assert!(bind_rune.schema().check_usage(KeyUsage::Verify).is_ok());
+    }
+}